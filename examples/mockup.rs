@@ -6,18 +6,18 @@ use bevy_ads_common::prelude::*;
 #[derive(Component, Default, Reflect)]
 #[reflect(Component)]
 #[require(Text)]
-struct AdMessagesHolder(Vec<String>);
+struct AdButtonText;
 
 #[derive(Component, Default, Reflect)]
 #[reflect(Component)]
-#[require(Text)]
-struct AdButtonText;
+#[require(Button)]
+struct AdButton;
 
 fn main() {
     App::new()
-        .register_type::<AdMessagesHolder>()
         .register_type::<AdButtonText>()
-        .add_plugins((DefaultPlugins, AdsCommonPlugin))
+        .register_type::<AdButton>()
+        .add_plugins((DefaultPlugins, AdsCommonPlugin::default()))
         .add_systems(Startup, setup)
         .add_systems(Update, on_message)
         .run();
@@ -51,10 +51,10 @@ fn setup(mut commands: Commands) {
                         ..Default::default()
                     },
                     BackgroundColor(color::palettes::tailwind::AMBER_400.into()),
-                    Button,
+                    AdButton,
                     children![(Text::new("Load Ad"), AdButtonText)]
                 ),
-                (AdMessagesHolder::default())
+                AdEventLog::default()
             ],
         ))
         .observe(on_click);
@@ -63,7 +63,7 @@ fn setup(mut commands: Commands) {
 fn on_click(
     _t: On<Pointer<Press>>,
     mut ads: MockupAdsSystem,
-    mut q: Query<&mut Visibility, With<Button>>,
+    mut q: Query<&mut Visibility, With<AdButton>>,
 ) {
     if ads.is_interstitial_ready() {
         ads.show_interstitial();
@@ -77,24 +77,11 @@ fn on_click(
 
 fn on_message(
     mut messages: MessageReader<AdMessage>,
-    mut q: Query<(&mut AdMessagesHolder, &mut Text)>,
-    mut btn_texts: Query<&mut Text, (With<AdButtonText>, Without<AdMessagesHolder>)>,
-    mut q2: Query<&mut Visibility, With<Button>>,
-    time: Res<Time>,
+    mut btn_texts: Query<&mut Text, With<AdButtonText>>,
+    mut q2: Query<&mut Visibility, With<AdButton>>,
 ) {
     for message in messages.read() {
-        for (mut ad_messages, mut text) in q.iter_mut() {
-            ad_messages
-                .0
-                .push(format!("{:.2}s: {:?}", time.elapsed_secs(), message));
-            text.0.clear();
-            text.0.push_str("Ads events:\n");
-            for mes in ad_messages.0.iter().rev() {
-                text.0.push_str(mes);
-                text.0.push('\n');
-            }
-        }
-        if let AdMessage::AdLoaded { ad_type: _ } = message {
+        if let AdMessage::AdLoaded { .. } = message {
             for mut v in q2.iter_mut() {
                 v.set_if_neq(Visibility::Inherited);
             }
@@ -103,7 +90,7 @@ fn on_message(
                 text.0.push_str("Show Ad");
             }
         }
-        if let AdMessage::AdClosed { ad_type: _ } = message {
+        if let AdMessage::AdClosed { .. } = message {
             for mut text in btn_texts.iter_mut() {
                 text.0.clear();
                 text.0.push_str("Load Ad");
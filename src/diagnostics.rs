@@ -0,0 +1,21 @@
+//! Timing diagnostics of the ad systems, recorded in the [`bevy_diagnostic::DiagnosticsStore`].
+//! Each diagnostic keeps the history of the last frames, in milliseconds.
+
+use bevy_app::App;
+use bevy_diagnostic::{Diagnostic, DiagnosticPath, RegisterDiagnostic};
+
+/// Time spent draining the event queue.
+pub const HANDLE_EVENTS: DiagnosticPath = DiagnosticPath::const_new("ads/handle_events");
+/// Time spent updating displayed mockup ads.
+#[cfg(feature = "mockup")]
+pub const MOCKUP_SHOW_ADS: DiagnosticPath = DiagnosticPath::const_new("ads/mockup/show_ads");
+/// Time spent updating the mockup fake loader.
+#[cfg(feature = "mockup")]
+pub const MOCKUP_FAKE_LOADER: DiagnosticPath = DiagnosticPath::const_new("ads/mockup/fake_loader");
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_diagnostic(Diagnostic::new(HANDLE_EVENTS).with_suffix("ms"));
+    #[cfg(feature = "mockup")]
+    app.register_diagnostic(Diagnostic::new(MOCKUP_SHOW_ADS).with_suffix("ms"))
+        .register_diagnostic(Diagnostic::new(MOCKUP_FAKE_LOADER).with_suffix("ms"));
+}
@@ -0,0 +1,107 @@
+//! Frequency capping shared by ads implementations.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::AdType;
+
+/// Limits how often ads are shown, enabled only while this resource is present.
+/// Ads implementations check [`FrequencyCap::allows`] before showing an ad, emit
+/// `AdThrottled` when it doesn't, and call [`FrequencyCap::record_shown`] once shown.
+/// Times are the elapsed app time, e.g. `Time::elapsed`.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct FrequencyCap {
+    /// Ad types the cap applies to.
+    pub ad_types: Vec<AdType>,
+    /// Minimum time between two shows of the same ad type.
+    pub min_interval: Duration,
+    /// Maximum number of shows of each ad type per session.
+    pub max_per_session: u32,
+    /// Number of shows per ad type this session.
+    shown: HashMap<AdType, u32>,
+    /// Time from which the next show is allowed per ad type.
+    next_show: HashMap<AdType, Duration>,
+}
+
+/// Shows recorded by a [`FrequencyCap`] this session, see [`FrequencyCap::session`].
+/// Cooldowns are relative, so a saved session can be restored after a restart.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub struct FrequencyCapSession {
+    /// Time left until the next show is allowed per ad type.
+    pub cooldowns: HashMap<AdType, Duration>,
+    /// Number of shows per ad type.
+    pub shown: HashMap<AdType, u32>,
+}
+
+impl Default for FrequencyCap {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(60), u32::MAX)
+    }
+}
+
+impl FrequencyCap {
+    /// Cap interstitial ads.
+    pub fn new(min_interval: Duration, max_per_session: u32) -> Self {
+        Self {
+            ad_types: vec![AdType::Interstitial],
+            min_interval,
+            max_per_session,
+            shown: HashMap::new(),
+            next_show: HashMap::new(),
+        }
+    }
+
+    /// Can an ad of this type be shown at `now` without violating the cap?
+    pub fn allows(&self, ad_type: AdType, now: Duration) -> bool {
+        if !self.ad_types.contains(&ad_type) {
+            return true;
+        }
+        if self.shown.get(&ad_type).copied().unwrap_or(0) >= self.max_per_session {
+            return false;
+        }
+        self.next_show.get(&ad_type).is_none_or(|next| now >= *next)
+    }
+
+    /// Record that an ad of this type was shown at `now`.
+    pub fn record_shown(&mut self, ad_type: AdType, now: Duration) {
+        self.next_show.insert(ad_type, now + self.min_interval);
+        *self.shown.entry(ad_type).or_default() += 1;
+    }
+
+    /// Start a new session, forgetting all recorded shows.
+    pub fn reset_session(&mut self) {
+        self.shown.clear();
+        self.next_show.clear();
+    }
+
+    /// Shows recorded this session, with the cooldowns left at `now`.
+    pub fn session(&self, now: Duration) -> FrequencyCapSession {
+        FrequencyCapSession {
+            cooldowns: self
+                .next_show
+                .iter()
+                .filter_map(|(ad_type, next)| {
+                    let left = next.saturating_sub(now);
+                    (!left.is_zero()).then_some((*ad_type, left))
+                })
+                .collect(),
+            shown: self.shown.clone(),
+        }
+    }
+
+    /// Continue a session saved with [`FrequencyCap::session`], its cooldowns
+    /// starting over at `now`.
+    pub fn restore_session(&mut self, session: FrequencyCapSession, now: Duration) {
+        self.shown = session.shown;
+        self.next_show = session
+            .cooldowns
+            .into_iter()
+            .map(|(ad_type, left)| (ad_type, now + left))
+            .collect();
+    }
+}
@@ -1,52 +1,380 @@
 #![doc = include_str!("../README.md")]
 use std::fmt::Display;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
-use bevy_app::{App, FixedUpdate, Plugin};
+use bevy_app::{App, FixedUpdate, Plugin, PreUpdate, Update};
 use bevy_ecs::prelude::*;
+use bevy_ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
+use bevy_ecs::system::SystemParam;
+use bevy_platform::time::Instant;
 use bevy_reflect::prelude::*;
 use crossbeam::queue::SegQueue;
-use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+mod frequency_cap;
+mod metrics;
 #[cfg(feature = "mockup")]
 mod mockup;
+mod noop;
+mod retry;
+#[cfg(feature = "states")]
+mod states;
+#[cfg(test)]
+mod tests;
+mod typed_reward;
+
+pub use frequency_cap::{FrequencyCap, FrequencyCapSession};
+pub use metrics::{AdCounts, AdMetrics};
+pub use noop::NoopAdManager;
+pub use retry::{AdLoadRetry, AdRetry, AdRetryState, RetryPolicy};
+#[cfg(feature = "states")]
+pub use states::{AdLifecycle, LIFECYCLE_LOG_TARGET};
+pub use typed_reward::{TypedReward, TypedRewardPlugin};
 
 pub mod prelude {
+    #[cfg(feature = "states")]
+    pub use crate::AdLifecycle;
+    #[cfg(feature = "audio")]
+    pub use crate::mockup::MockupAudioCues;
     #[cfg(feature = "mockup")]
     pub use crate::mockup::{
-        AdDisplay, AdDisplaySettings, MockupAdComponent, MockupAdTimeLeftText, MockupAdType,
-        MockupAds, MockupAdsSystem,
+        AdAnimations, AdDisplay, AdDisplaySettings, AdDisplaySettingsBuilder, AdEventLog,
+        CloseButtonStyle, EarlyCloseReward, GradientDirection, ImageFit, InterstitialPacing,
+        InterstitialTrigger, MockupAdAnimation, MockupAdAnimations, MockupAdCloseButton,
+        MockupAdCloseableAfter, MockupAdComponent, MockupAdImageFit, MockupAdNetwork,
+        MockupAdPendingImpression, MockupAdProgressBar, MockupAdSkipAfter, MockupAdSkipButton,
+        MockupAdSkipped, MockupAdSpawnHook, MockupAdStrings, MockupAdTimeLeftText, MockupAdType,
+        MockupAds, MockupAdsBuilder, MockupAdsSystem, MockupBannerClickThrough,
+        MockupChainedReward, MockupConsentForm, MockupMutedIcon, MockupOptInAnswer,
+        MockupOptInPrompt, MockupRewardOverride, MockupSnapshot, MockupTimePause, MockupTimers,
+        NativeAdLayout, ShowAnimation, TimeLeftFormat, TimeLeftStyle, active_ad_entity, ad_ready,
+        any_ad_showing, interstitial_ready, no_ad_showing, rewarded_ready,
+        show_interstitial_every_nth,
+    };
+    #[cfg(feature = "config_asset")]
+    pub use crate::mockup::{
+        AdDisplayConfig, MockupAdsConfig, MockupAdsConfigHandle, MockupAdsConfigLoader,
+        MockupAdsConfigLoaderError,
     };
-    pub use crate::{AdManager, AdMessage, AdType, AdsCommonPlugin};
+    pub use crate::{
+        AdCloseReason, AdCounts, AdError, AdEventQueue, AdEvents, AdEventsDrained, AdLoadRetry,
+        AdManager, AdMessage, AdMetrics, AdQueueStats, AdSystems, AdType, AdsCommonPlugin,
+        AdsEnabled, ConsentState, ConsentStatus, DrainSchedule, FrequencyCap, FrequencyCapSession,
+        NoopAdManager, PendingRewards, QueueOverflowPolicy, RetryPolicy, Reward, TypedReward,
+        TypedRewardPlugin,
+    };
+}
+
+/// Queue of ad events of a single [`App`], written by the ads implementation and
+/// drained into [`AdMessage`]s every `FixedUpdate`.
+/// Cloning is cheap and yields a thread-safe handle to the same queue, so platform
+/// callbacks can keep one obtained at build time.
+#[derive(Resource, Clone)]
+pub struct AdEventQueue(Arc<EventQueueInner>);
+
+struct EventQueueInner {
+    queue: SegQueue<QueuedEvent>,
+    capacity: usize,
+    policy: QueueOverflowPolicy,
+    max_event_age: Option<Duration>,
+    trigger_events: bool,
+    dropped_events: AtomicU64,
+}
+
+struct QueuedEvent {
+    event: AdMessage,
+    queued_at: Instant,
+}
+
+impl Default for AdEventQueue {
+    fn default() -> Self {
+        Self::new(&AdsCommonPlugin::default())
+    }
+}
+
+impl AdEventQueue {
+    fn new(config: &AdsCommonPlugin) -> Self {
+        Self(Arc::new(EventQueueInner {
+            queue: SegQueue::new(),
+            capacity: config.max_queued_events,
+            policy: config.overflow_policy,
+            max_event_age: config.max_event_age,
+            trigger_events: config.trigger_events,
+            dropped_events: AtomicU64::new(0),
+        }))
+    }
+
+    /// Write an event to the queue.
+    /// In almost all cases this should be called only by the ads implementation plugin.
+    /// The queue is bounded according to [`AdsCommonPlugin::overflow_policy`].
+    pub fn write(&self, event: AdMessage) {
+        let inner = &self.0;
+        let event = QueuedEvent {
+            event,
+            queued_at: Instant::now(),
+        };
+        match inner.policy {
+            QueueOverflowPolicy::Unbounded => inner.queue.push(event),
+            QueueOverflowPolicy::DropNewest => {
+                if inner.queue.len() >= inner.capacity {
+                    inner.dropped_events.fetch_add(1, Ordering::Relaxed);
+                    return;
+                }
+                inner.queue.push(event);
+            }
+            QueueOverflowPolicy::DropOldest => {
+                inner.queue.push(event);
+                while inner.queue.len() > inner.capacity && inner.queue.pop().is_some() {
+                    inner.dropped_events.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Pop every queued event in FIFO order, discarding stale ones.
+    /// In normal app usage the events are drained by the plugin every `FixedUpdate`,
+    /// this is meant for tests and custom schedulers.
+    pub fn drain(&self) -> Vec<AdMessage> {
+        let mut stale = 0;
+        std::iter::from_fn(|| self.pop_fresh(&mut stale)).collect()
+    }
+
+    /// Number of events currently waiting in the queue.
+    pub fn len(&self) -> usize {
+        self.0.queue.len()
+    }
+
+    /// Is the queue empty?
+    pub fn is_empty(&self) -> bool {
+        self.0.queue.is_empty()
+    }
+
+    /// Total number of events dropped because the queue was full.
+    pub fn dropped_events(&self) -> u64 {
+        self.0.dropped_events.load(Ordering::Relaxed)
+    }
+
+    /// Pop the oldest event that is not older than [`AdsCommonPlugin::max_event_age`],
+    /// counting the discarded stale events in `stale`.
+    fn pop_fresh(&self, stale: &mut usize) -> Option<AdMessage> {
+        while let Some(queued) = self.0.queue.pop() {
+            if self
+                .0
+                .max_event_age
+                .is_some_and(|max_age| queued.queued_at.elapsed() > max_age)
+            {
+                *stale += 1;
+                continue;
+            }
+            return Some(queued.event);
+        }
+        None
+    }
 }
 
-static EVENT_QUEUE: Lazy<SegQueue<AdMessage>> = Lazy::new(SegQueue::new);
+/// What to do with events written while the queue holds
+/// [`AdsCommonPlugin::max_queued_events`] events.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum QueueOverflowPolicy {
+    /// Discard the oldest queued event to make room for the new one.
+    DropOldest,
+    /// Discard the new event.
+    DropNewest,
+    /// Never drop events, `max_queued_events` is ignored.
+    #[default]
+    Unbounded,
+}
+
+/// Schedule the ad event queue is drained in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum DrainSchedule {
+    /// Drain in `FixedUpdate`, events may wait for the next fixed step.
+    #[default]
+    FixedUpdate,
+    /// Drain in `Update` for lower latency.
+    Update,
+    /// Drain in `PreUpdate`, before any gameplay system of the frame.
+    PreUpdate,
+}
 
-/// Write an event to the queue.
-/// In almost all cases this should be called only by the ads implementation plugin.
-pub fn write_event_to_queue(event: AdMessage) {
-    EVENT_QUEUE.push(event);
+impl DrainSchedule {
+    /// Label of the schedule.
+    pub fn label(self) -> InternedScheduleLabel {
+        match self {
+            DrainSchedule::FixedUpdate => FixedUpdate.intern(),
+            DrainSchedule::Update => Update.intern(),
+            DrainSchedule::PreUpdate => PreUpdate.intern(),
+        }
+    }
 }
 
-/// Events that can be triggered by Ad system operations
-#[derive(Message, Debug, Clone, Reflect, Serialize, Deserialize)]
+/// Statistics of the ad event queue.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct AdQueueStats {
+    /// Total number of events dropped because the queue was full.
+    pub dropped_events: u64,
+}
+
+/// Events that can be triggered by Ad system operations.
+/// Also triggered for observers when [`AdsCommonPlugin::trigger_events`] is set.
+#[derive(Message, Event, Debug, Clone, Reflect, Serialize, Deserialize)]
 pub enum AdMessage {
-    /// Ad system completed initialization.
+    /// Ad system completed initialization, emitted only once [`AdManager::initialize`] ran,
+    /// see [`AdsCommonPlugin::auto_initialize`].
     Initialized { success: bool },
     /// Consent was gathered.
-    ConsentGathered { success: bool, error: String },
-    /// Ad was loaded.
-    AdLoaded { ad_type: AdType },
+    ConsentGathered {
+        success: bool,
+        /// Why gathering consent failed, `None` on success.
+        error: Option<AdError>,
+        state: ConsentState,
+    },
+    /// Ad was loaded, by the given ad network when known.
+    ///
+    /// `ad_id` of the lifecycle messages is the ad unit id passed to [`AdManager::load_ad`],
+    /// telling apart placements of the same ad type.
+    AdLoaded {
+        ad_type: AdType,
+        ad_id: String,
+        network: Option<String>,
+        /// Whether the ad is personalized, false when loaded without
+        /// [`ConsentState::personalized_ads`].
+        personalized: bool,
+    },
     /// Ad failed to load.
-    AdFailedToLoad { ad_type: AdType, error: String },
+    AdFailedToLoad {
+        ad_type: AdType,
+        ad_id: String,
+        error: AdError,
+    },
     /// A loaded ad failed to present (e.g. another fullscreen ad is already showing).
     AdFailedToPresent { ad_type: AdType, error: String },
-    /// Ad was opened.
-    AdOpened { ad_type: AdType },
+    /// Ad was opened, when it is displayed and for banners also when a click opens the ad.
+    AdOpened { ad_type: AdType, ad_id: String },
+    /// Ad was rendered on screen, fired after `AdOpened` once the ad is actually visible.
+    AdImpression { ad_type: AdType },
+    /// User clicked the displayed ad.
+    AdClicked { ad_type: AdType },
+    /// Showing an ad was refused because it would violate the [`FrequencyCap`].
+    AdThrottled { ad_type: AdType },
+    /// User skipped the ad before it completed, no reward is granted.
+    AdSkipped { ad_type: AdType },
     /// Ad was closed.
-    AdClosed { ad_type: AdType },
+    /// For a completed rewarded ad it always follows its `RewardedAdEarnedReward`.
+    AdClosed {
+        ad_type: AdType,
+        ad_id: String,
+        reason: AdCloseReason,
+    },
+    /// Revenue paid for an ad impression, modeled after AdMob's paid event.
+    AdRevenuePaid {
+        ad_type: AdType,
+        /// Revenue in micros of `currency`.
+        value_micros: i64,
+        /// ISO 4217 currency code.
+        currency: String,
+        /// Precision of the value reported by the network, e.g. `ESTIMATED` or `PRECISE`.
+        precision: String,
+    },
+    /// Size of the displayed banner in pixels, emitted when it is shown and whenever its
+    /// size changes, e.g. an adaptive banner after the window was resized.
+    BannerSizeChanged { width: i32, height: i32 },
+    /// Showing an ad was intentionally skipped, e.g. the onboarding "first ad free" skip
+    /// or while [`AdsEnabled`] is false.
+    AdSuppressed { ad_type: AdType },
+    /// The ad network flagged anomalous activity, e.g. a reward claimed faster than
+    /// the ad could be watched.
+    SuspiciousActivity { reason: String },
+    /// Rewarded ad started playing, carrying the reward it grants on completion.
+    RewardedAdStarted { expected_reward: Reward },
     /// Rewarded ad earned reward.
+    /// Always emitted before the `AdClosed` of the same ad, so the reward can be
+    /// processed before teardown.
     RewardedAdEarnedReward { amount: i32, reward_type: String },
+    /// Combined reward of a chained "double or nothing" rewarded ad, replacing the
+    /// `replaces` amount of the reward it multiplied. Only the difference is still owed,
+    /// see [`AdMessage::owed_reward`].
+    /// Like `RewardedAdEarnedReward` it is emitted before the `AdClosed` of the same ad.
+    ChainedRewardEarned {
+        amount: i32,
+        reward_type: String,
+        replaces: i32,
+    },
+}
+
+impl AdMessage {
+    /// Ad type the message refers to, `None` for messages not tied to an ad.
+    pub fn ad_type(&self) -> Option<AdType> {
+        match self {
+            AdMessage::Initialized { .. }
+            | AdMessage::ConsentGathered { .. }
+            | AdMessage::SuspiciousActivity { .. } => None,
+            AdMessage::AdLoaded { ad_type, .. }
+            | AdMessage::AdFailedToLoad { ad_type, .. }
+            | AdMessage::AdFailedToPresent { ad_type, .. }
+            | AdMessage::AdOpened { ad_type, .. }
+            | AdMessage::AdImpression { ad_type }
+            | AdMessage::AdClicked { ad_type }
+            | AdMessage::AdSkipped { ad_type }
+            | AdMessage::AdThrottled { ad_type }
+            | AdMessage::AdClosed { ad_type, .. }
+            | AdMessage::AdRevenuePaid { ad_type, .. }
+            | AdMessage::AdSuppressed { ad_type } => Some(*ad_type),
+            AdMessage::BannerSizeChanged { .. } => Some(AdType::Banner),
+            AdMessage::RewardedAdStarted { .. }
+            | AdMessage::RewardedAdEarnedReward { .. }
+            | AdMessage::ChainedRewardEarned { .. } => Some(AdType::Rewarded),
+        }
+    }
+
+    /// Reward the player is owed for this message, `None` for messages granting none.
+    /// For a `ChainedRewardEarned` it is what the combined reward adds to the one it replaces.
+    pub fn owed_reward(&self) -> Option<Reward> {
+        match self {
+            AdMessage::RewardedAdEarnedReward {
+                amount,
+                reward_type,
+            } => Some(Reward {
+                amount: *amount,
+                type_name: reward_type.clone(),
+            }),
+            AdMessage::ChainedRewardEarned {
+                amount,
+                reward_type,
+                replaces,
+            } => Some(Reward {
+                amount: amount.saturating_sub(*replaces),
+                type_name: reward_type.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Reader of [`AdMessage`]s that can filter them by ad type.
+#[derive(SystemParam)]
+pub struct AdEvents<'w, 's> {
+    reader: MessageReader<'w, 's, AdMessage>,
+}
+
+impl AdEvents<'_, '_> {
+    /// Read all unread messages.
+    pub fn iter(&mut self) -> impl Iterator<Item = &AdMessage> {
+        self.reader.read()
+    }
+
+    /// Read all unread messages, yielding only the ones referring to `ad_type`.
+    /// Messages of other ad types are marked as read as well.
+    pub fn iter_for(&mut self, ad_type: AdType) -> impl Iterator<Item = &AdMessage> {
+        self.reader
+            .read()
+            .filter(move |message| message.ad_type() == Some(ad_type))
+    }
 }
 
 /// Ad type description enum.
@@ -58,6 +386,168 @@ pub enum AdType {
     Interstitial,
     /// Rewarded ad type
     Rewarded,
+    /// App open ad type, shown when the player returns to the app
+    AppOpen,
+    /// Native ad type, displayed inline with the game UI
+    Native,
+}
+
+/// Standard banner ad sizes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum BannerSize {
+    /// 320x50 banner.
+    #[default]
+    Standard,
+    /// 320x100 large banner.
+    Large,
+    /// 300x250 medium rectangle (MREC).
+    MediumRectangle,
+    /// 468x60 full banner.
+    FullWidth,
+    /// Banner spanning the screen width with a 50px height.
+    Adaptive,
+}
+
+impl BannerSize {
+    /// Height of the banner in pixels.
+    pub fn height(&self) -> i32 {
+        match self {
+            BannerSize::Standard | BannerSize::Adaptive => 50,
+            BannerSize::Large => 100,
+            BannerSize::MediumRectangle => 250,
+            BannerSize::FullWidth => 60,
+        }
+    }
+
+    /// Width of the banner in pixels, `screen_width` is used by adaptive banners.
+    pub fn width(&self, screen_width: i32) -> i32 {
+        match self {
+            BannerSize::Standard | BannerSize::Large => 320,
+            BannerSize::MediumRectangle => 300,
+            BannerSize::FullWidth => 468,
+            BannerSize::Adaptive => screen_width,
+        }
+    }
+}
+
+/// Position of the banner on the screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect, Serialize, Deserialize)]
+pub enum BannerPosition {
+    /// Top edge, horizontally centered.
+    Top,
+    /// Bottom edge, horizontally centered.
+    #[default]
+    Bottom,
+    /// Top left corner of the screen.
+    TopLeft,
+    /// Top right corner of the screen.
+    TopRight,
+    /// Bottom left corner of the screen.
+    BottomLeft,
+    /// Bottom right corner of the screen.
+    BottomRight,
+    /// Offset of the banner's top left corner from the top left of the screen, in pixels.
+    Custom { top: f32, left: f32 },
+}
+
+/// Reward for displaying an rewarded ad.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub struct Reward {
+    pub amount: i32,
+    pub type_name: String,
+}
+
+impl Default for Reward {
+    fn default() -> Self {
+        Self {
+            amount: 1,
+            type_name: "default".to_string(),
+        }
+    }
+}
+
+/// Reason for closing an ad.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum AdCloseReason {
+    /// Ad was shown and then closed.
+    #[default]
+    Closed,
+    /// Ad was skipped before being shown (e.g. the player paid to skip it).
+    Skipped,
+    /// Player declined to watch the ad.
+    UserDismissed,
+}
+
+/// Category of an ad failure, displayed as a human readable message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum AdError {
+    /// No ad was available to fill the request.
+    NoFill,
+    /// The request failed because of a network error.
+    NetworkError,
+    /// The request didn't complete in time.
+    Timeout,
+    /// The ad unit id is invalid.
+    InvalidAdId,
+    /// The ads SDK is not initialized yet.
+    NotInitialized,
+    /// Any other failure, with the message reported by the ads SDK.
+    Internal(String),
+}
+
+impl Display for AdError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdError::NoFill => write!(f, "no ad available"),
+            AdError::NetworkError => write!(f, "network error"),
+            AdError::Timeout => write!(f, "request timed out"),
+            AdError::InvalidAdId => write!(f, "invalid ad id"),
+            AdError::NotInitialized => write!(f, "ads are not initialized"),
+            AdError::Internal(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for AdError {}
+
+impl From<String> for AdError {
+    fn from(message: String) -> Self {
+        AdError::Internal(message)
+    }
+}
+
+impl From<&str> for AdError {
+    fn from(message: &str) -> Self {
+        AdError::Internal(message.to_string())
+    }
+}
+
+/// Per-purpose consent given by the user, modeled after GDPR/TCF purposes.
+///
+/// When `personalized_ads` is false only non-personalized ads should be served,
+/// ads themselves are not disabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub struct ConsentState {
+    /// Storing and accessing information on the device.
+    pub storage: bool,
+    /// Measuring ad performance and collecting analytics.
+    pub analytics: bool,
+    /// Serving ads personalized to the user.
+    pub personalized_ads: bool,
+}
+
+/// Where the user is in the consent flow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum ConsentStatus {
+    /// Consent wasn't requested yet.
+    #[default]
+    Unknown,
+    /// Consent is required but wasn't obtained yet.
+    Required,
+    /// Consent is not required in the user's region.
+    NotRequired,
+    /// Consent was obtained.
+    Obtained,
 }
 
 /// Error type for parsing ad type.
@@ -75,6 +565,8 @@ impl TryFrom<&str> for AdType {
             "banner" | "Banner" => Ok(AdType::Banner),
             "interstitial" | "Interstitial" => Ok(AdType::Interstitial),
             "rewarded" | "Rewarded" => Ok(AdType::Rewarded),
+            "app_open" | "AppOpen" => Ok(AdType::AppOpen),
+            "native" | "Native" => Ok(AdType::Native),
             _ => Err(ParsingAdTypeError::InvalidValue),
         }
     }
@@ -86,11 +578,39 @@ impl Display for AdType {
             AdType::Banner => write!(f, "banner"),
             AdType::Interstitial => write!(f, "interstitial"),
             AdType::Rewarded => write!(f, "rewarded"),
+            AdType::AppOpen => write!(f, "app_open"),
+            AdType::Native => write!(f, "native"),
         }
     }
 }
 
 /// Trait for managing ads system.
+///
+/// The trait is object safe, so implementations can be swapped at runtime behind a `dyn AdManager`:
+///
+/// ```
+/// use bevy_ads_common::prelude::*;
+///
+/// struct NoAds;
+///
+/// impl AdManager for NoAds {
+/// #   fn initialize(&mut self) -> bool { true }
+/// #   fn is_initialized(&self) -> bool { true }
+/// #   fn show_banner(&mut self) -> bool { false }
+/// #   fn show_interstitial(&mut self) -> bool { false }
+/// #   fn show_rewarded(&mut self) -> bool { false }
+/// #   fn hide_banner(&mut self) -> bool { true }
+/// #   fn hide_interstitial(&mut self) -> bool { true }
+/// #   fn hide_rewarded(&mut self) -> bool { true }
+///     fn load_banner(&mut self, _ad_id: &str) -> bool { false }
+///     // ...
+/// #   fn load_interstitial(&mut self, _ad_id: &str) -> bool { false }
+/// #   fn load_rewarded(&mut self, _ad_id: &str) -> bool { false }
+/// }
+///
+/// let mut manager: Box<dyn AdManager> = Box::new(NoAds);
+/// assert!(!manager.load_ad(AdType::Interstitial, "id"));
+/// ```
 pub trait AdManager {
     /// Initialize the AdManager.
     /// Returns true if it was able to start initialization process.
@@ -104,6 +624,8 @@ pub trait AdManager {
             AdType::Banner => self.load_banner(ad_id),
             AdType::Interstitial => self.load_interstitial(ad_id),
             AdType::Rewarded => self.load_rewarded(ad_id),
+            AdType::AppOpen => self.load_app_open(ad_id),
+            AdType::Native => self.load_native(ad_id),
         }
     }
     /// Show an ad of the specified type.
@@ -116,6 +638,8 @@ pub trait AdManager {
             AdType::Banner => self.show_banner(),
             AdType::Interstitial => self.show_interstitial(),
             AdType::Rewarded => self.show_rewarded(),
+            AdType::AppOpen => self.show_app_open(),
+            AdType::Native => self.show_native(),
         }
     }
     /// Hide an ad of the specified type.
@@ -125,8 +649,16 @@ pub trait AdManager {
             AdType::Banner => self.hide_banner(),
             AdType::Interstitial => self.hide_interstitial(),
             AdType::Rewarded => self.hide_rewarded(),
+            AdType::AppOpen => self.hide_app_open(),
+            AdType::Native => self.hide_native(),
         }
     }
+    /// Fully dispose an ad of the specified type, releasing the resources it holds.
+    /// Unlike [`AdManager::hide_ad`] the ad has to be loaded again before it can be shown.
+    /// Returns true if the ad was successfully destroyed.
+    fn destroy_ad(&mut self, ad_type: AdType) -> bool {
+        self.hide_ad(ad_type)
+    }
     /// Check if an ad of the specified type is ready to be shown.
     /// Returns true if the ad is ready.
     fn is_ad_ready(&self, ad_type: AdType) -> bool {
@@ -134,6 +666,8 @@ pub trait AdManager {
             AdType::Banner => self.is_banner_ready(),
             AdType::Interstitial => self.is_interstitial_ready(),
             AdType::Rewarded => self.is_rewarded_ready(),
+            AdType::AppOpen => self.is_app_open_ready(),
+            AdType::Native => self.is_native_ready(),
         }
     }
     /// Show a banner ad.
@@ -175,6 +709,130 @@ pub trait AdManager {
     fn is_rewarded_ready(&self) -> bool {
         false
     }
+    /// Load an app open ad.
+    /// Returns true if the ad was successfully loaded.
+    fn load_app_open(&mut self, _ad_id: &str) -> bool {
+        false
+    }
+    /// Show an app open ad.
+    /// Returns true if the ad was successfully shown.
+    fn show_app_open(&mut self) -> bool {
+        false
+    }
+    /// Hide an app open ad.
+    /// Returns true if the ad was successfully hidden.
+    fn hide_app_open(&mut self) -> bool {
+        false
+    }
+    /// Is an app open ad ready to be shown?
+    fn is_app_open_ready(&self) -> bool {
+        false
+    }
+    /// Load a native ad.
+    /// Returns true if the ad was successfully loaded.
+    fn load_native(&mut self, _ad_id: &str) -> bool {
+        false
+    }
+    /// Show a native ad.
+    /// Returns true if the ad was successfully shown.
+    fn show_native(&mut self) -> bool {
+        false
+    }
+    /// Hide a native ad.
+    /// Returns true if the ad was successfully hidden.
+    fn hide_native(&mut self) -> bool {
+        false
+    }
+    /// Is a native ad ready to be shown?
+    fn is_native_ready(&self) -> bool {
+        false
+    }
+
+    /// Number of loaded ads of this type ready to be shown, for implementations
+    /// preloading several ads of the same type.
+    fn loaded_count(&self, ad_type: AdType) -> usize {
+        usize::from(self.is_ad_ready(ad_type))
+    }
+
+    /// Check if an ad of the specified type is being loaded, i.e. a load was started
+    /// and neither `AdLoaded` nor `AdFailedToLoad` was emitted yet.
+    fn is_loading(&self, _ad_type: AdType) -> bool {
+        false
+    }
+
+    /// Name of the ad network that filled the loaded ad of this type, if known.
+    fn loaded_ad_network(&self, _ad_type: AdType) -> Option<String> {
+        None
+    }
+
+    /// Set the volume of ads playing sound, in `0.0..=1.0`.
+    fn set_ad_volume(&mut self, _volume: f32) {}
+
+    /// Mute or unmute ads, e.g. to follow the game's mute state.
+    fn set_muted(&mut self, _muted: bool) {}
+
+    /// Apply the consent given by the user.
+    fn set_consent(&mut self, _state: ConsentState) {}
+
+    /// Get the currently applied consent.
+    fn consent_state(&self) -> ConsentState {
+        ConsentState::default()
+    }
+
+    /// Start gathering consent, completed with `ConsentGathered`.
+    /// Returns true if the consent flow was started.
+    fn request_consent(&mut self) -> bool {
+        false
+    }
+
+    /// Get the status of the consent flow.
+    fn consent_status(&self) -> ConsentStatus {
+        ConsentStatus::Unknown
+    }
+
+    /// Does the user's region require gathering consent before showing ads?
+    /// Implementations without region information should keep the default of true.
+    fn consent_required_in_region(&self) -> bool {
+        true
+    }
+
+    /// Enable or disable all ads at runtime, e.g. after a "remove ads" purchase.
+    /// Disabling hides the displayed ads, implementations should also update [`AdsEnabled`]
+    /// and refuse to load or show ads while disabled.
+    fn set_ads_enabled(&mut self, enabled: bool) {
+        if enabled {
+            return;
+        }
+        for ad_type in [
+            AdType::Banner,
+            AdType::Interstitial,
+            AdType::Rewarded,
+            AdType::AppOpen,
+            AdType::Native,
+        ] {
+            self.hide_ad(ad_type);
+        }
+    }
+
+    /// Remove every displayed ad, e.g. on a scene transition.
+    /// Implementations should also discard loaded ads so none is ready afterwards.
+    fn dismiss_all(&mut self) {
+        for ad_type in [
+            AdType::Banner,
+            AdType::Interstitial,
+            AdType::Rewarded,
+            AdType::AppOpen,
+            AdType::Native,
+        ] {
+            self.hide_ad(ad_type);
+        }
+    }
+
+    /// Refresh the displayed banner with a new ad every `interval`, `None` disables refreshing.
+    fn set_banner_refresh_interval(&mut self, _interval: Option<Duration>) {}
+
+    /// Move the banner, including an already displayed one.
+    fn set_banner_position(&mut self, _position: BannerPosition) {}
 
     /// Get the width of the banner ad.
     fn get_banner_width(&self, _ad_id: &str) -> i32 {
@@ -187,22 +845,208 @@ pub trait AdManager {
     }
 }
 
+/// System sets of [`AdsCommonPlugin`].
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AdSystems {
+    /// Drains the event queue in [`AdsCommonPlugin::drain_schedule`].
+    /// Systems ordered after it see all events queued so far through `MessageReader<AdMessage>`.
+    DrainQueue,
+}
+
 /// Basic plugin for managing ads.
 /// It provides a set of methods alongside a optional mockup ads implementation.
-pub struct AdsCommonPlugin;
+#[derive(Debug, Clone)]
+pub struct AdsCommonPlugin {
+    /// Maximum number of events waiting in the queue, e.g. while the schedule is paused.
+    pub max_queued_events: usize,
+    /// What to do with events once the queue is full.
+    pub overflow_policy: QueueOverflowPolicy,
+    /// Events waiting longer than this, e.g. while the app was paused, are discarded.
+    pub max_event_age: Option<Duration>,
+    /// Schedule the event queue is drained in, e.g. [`DrainSchedule::Update`] for lower latency.
+    pub drain_schedule: DrainSchedule,
+    /// Pause `Time<Virtual>` while a fullscreen mockup ad is displayed, so gameplay
+    /// doesn't keep running behind it. Banners and native ads don't pause.
+    pub pause_on_fullscreen: bool,
+    /// Also trigger every drained [`AdMessage`] for observers, e.g. `On<AdMessage>`,
+    /// in addition to writing it as a message.
+    pub trigger_events: bool,
+    /// Initialize the mockup on startup. Disable it to call [`AdManager::initialize`]
+    /// yourself, e.g. once consent was gathered.
+    pub auto_initialize: bool,
+    /// Asset path of a `*.ads.ron` [`MockupAdsConfig`](prelude::MockupAdsConfig) loaded on startup
+    /// and applied to the mockup on every hot reload.
+    #[cfg(feature = "config_asset")]
+    pub mockup_config: Option<String>,
+}
+
+impl Default for AdsCommonPlugin {
+    fn default() -> Self {
+        Self {
+            max_queued_events: 256,
+            overflow_policy: QueueOverflowPolicy::Unbounded,
+            max_event_age: None,
+            drain_schedule: DrainSchedule::FixedUpdate,
+            pause_on_fullscreen: false,
+            trigger_events: false,
+            auto_initialize: true,
+            #[cfg(feature = "config_asset")]
+            mockup_config: None,
+        }
+    }
+}
+
+impl AdsCommonPlugin {
+    /// Plugin with the default settings, customize it with the setters below.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Set [`AdsCommonPlugin::max_queued_events`].
+    pub fn max_queued_events(mut self, max_queued_events: usize) -> Self {
+        self.max_queued_events = max_queued_events;
+        self
+    }
+    /// Set [`AdsCommonPlugin::overflow_policy`].
+    pub fn overflow_policy(mut self, overflow_policy: QueueOverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        self
+    }
+    /// Set [`AdsCommonPlugin::max_event_age`], `None` keeps events until drained.
+    pub fn max_event_age(mut self, max_event_age: Option<Duration>) -> Self {
+        self.max_event_age = max_event_age;
+        self
+    }
+    /// Set [`AdsCommonPlugin::drain_schedule`].
+    pub fn drain_schedule(mut self, drain_schedule: DrainSchedule) -> Self {
+        self.drain_schedule = drain_schedule;
+        self
+    }
+    /// Set [`AdsCommonPlugin::pause_on_fullscreen`].
+    pub fn pause_on_fullscreen(mut self, pause_on_fullscreen: bool) -> Self {
+        self.pause_on_fullscreen = pause_on_fullscreen;
+        self
+    }
+    /// Set [`AdsCommonPlugin::trigger_events`].
+    pub fn trigger_events(mut self, trigger_events: bool) -> Self {
+        self.trigger_events = trigger_events;
+        self
+    }
+    /// Set [`AdsCommonPlugin::auto_initialize`].
+    pub fn auto_initialize(mut self, auto_initialize: bool) -> Self {
+        self.auto_initialize = auto_initialize;
+        self
+    }
+    /// Load the mockup configuration from the asset at `path`,
+    /// see [`AdsCommonPlugin::mockup_config`].
+    #[cfg(feature = "config_asset")]
+    pub fn mockup_config(mut self, path: impl Into<String>) -> Self {
+        self.mockup_config = Some(path.into());
+        self
+    }
+}
 
 impl Plugin for AdsCommonPlugin {
     fn build(&self, app: &mut App) {
-        app.add_message::<AdMessage>()
-            .add_systems(FixedUpdate, handle_events)
-            .register_type::<AdMessage>();
+        app.insert_resource(AdEventQueue::new(self))
+            .add_message::<AdMessage>()
+            .init_resource::<AdQueueStats>()
+            .init_resource::<PendingRewards>()
+            .init_resource::<AdsEnabled>()
+            .add_systems(
+                self.drain_schedule.label(),
+                handle_events.in_set(AdSystems::DrainQueue),
+            )
+            .register_type::<AdMessage>()
+            .register_type::<AdType>()
+            .register_type::<AdQueueStats>()
+            .register_type::<PendingRewards>()
+            .register_type::<AdsEnabled>()
+            .register_type::<FrequencyCap>();
+        app.add_plugins((retry::plugin, metrics::plugin));
+        #[cfg(feature = "states")]
+        app.add_plugins(states::plugin);
+        #[cfg(feature = "diagnostics")]
+        app.add_plugins(diagnostics::plugin);
         #[cfg(feature = "mockup")]
         app.add_plugins(mockup::plugin);
+        #[cfg(feature = "mockup")]
+        if self.auto_initialize {
+            app.add_systems(bevy_app::PostStartup, mockup::init);
+        }
+        #[cfg(feature = "mockup")]
+        if self.pause_on_fullscreen {
+            app.init_resource::<mockup::MockupTimePause>();
+        }
+        #[cfg(feature = "config_asset")]
+        if let Some(path) = &self.mockup_config {
+            mockup::config::load_on_startup(app, path.clone());
+        }
+    }
+}
+
+/// Whether ads are enabled, e.g. false once the player bought "remove ads".
+/// Toggle it with [`AdManager::set_ads_enabled`] so displayed ads are hidden as well.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct AdsEnabled(pub bool);
+
+impl Default for AdsEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Rewards earned from rewarded ads that the game didn't apply yet.
+/// Unlike [`AdMessage::RewardedAdEarnedReward`] they are kept until taken.
+#[derive(Resource, Debug, Default, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct PendingRewards(pub Vec<Reward>);
+
+impl PendingRewards {
+    /// Take all pending rewards to apply them.
+    pub fn take_pending_rewards(&mut self) -> Vec<Reward> {
+        std::mem::take(&mut self.0)
     }
 }
 
-fn handle_events(mut writer: MessageWriter<AdMessage>) {
-    while let Some(ev) = EVENT_QUEUE.pop() {
+/// Triggered once per run of the event queue drain, after all queued [`AdMessage`]s
+/// were written, with the number of messages written.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdEventsDrained {
+    pub count: usize,
+}
+
+fn handle_events(
+    mut writer: MessageWriter<AdMessage>,
+    mut commands: Commands,
+    queue: Res<AdEventQueue>,
+    mut stats: ResMut<AdQueueStats>,
+    mut pending_rewards: ResMut<PendingRewards>,
+    #[cfg(feature = "diagnostics")] mut diagnostics: bevy_diagnostic::Diagnostics,
+) {
+    #[cfg(feature = "diagnostics")]
+    let start = Instant::now();
+    stats.set_if_neq(AdQueueStats {
+        dropped_events: queue.dropped_events(),
+    });
+    let mut count = 0;
+    let mut stale = 0;
+    while let Some(ev) = queue.pop_fresh(&mut stale) {
+        if let Some(reward) = ev.owed_reward() {
+            pending_rewards.0.push(reward);
+        }
+        if queue.0.trigger_events {
+            commands.trigger(ev.clone());
+        }
         writer.write(ev);
+        count += 1;
+    }
+    if stale > 0 {
+        bevy_log::info!("Dropped {stale} stale ad events");
     }
+    commands.trigger(AdEventsDrained { count });
+    #[cfg(feature = "diagnostics")]
+    diagnostics.add_measurement(&diagnostics::HANDLE_EVENTS, || {
+        start.elapsed().as_secs_f64() * 1000.0
+    });
 }
@@ -0,0 +1,113 @@
+//! Running totals of ad events, e.g. for a debug overlay.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use bevy_app::{App, Update};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+
+use crate::{AdMessage, AdType};
+
+/// Ad event counters, see [`AdMetrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub struct AdCounts {
+    pub loads: u64,
+    pub load_failures: u64,
+    pub impressions: u64,
+    pub clicks: u64,
+    pub closes: u64,
+    pub rewards_granted: u64,
+    /// Sum of [`AdMessage::AdRevenuePaid`] values, regardless of their currency.
+    pub total_revenue_micros: i64,
+}
+
+impl AdCounts {
+    fn record(&mut self, message: &AdMessage) {
+        match message {
+            AdMessage::AdLoaded { .. } => self.loads += 1,
+            AdMessage::AdFailedToLoad { .. } => self.load_failures += 1,
+            AdMessage::AdImpression { .. } => self.impressions += 1,
+            AdMessage::AdClicked { .. } => self.clicks += 1,
+            AdMessage::AdClosed { .. } => self.closes += 1,
+            AdMessage::RewardedAdEarnedReward { .. } | AdMessage::ChainedRewardEarned { .. } => {
+                self.rewards_granted += 1;
+            }
+            AdMessage::AdRevenuePaid { value_micros, .. } => {
+                self.total_revenue_micros += value_micros;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Display for AdCounts {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "loads: {}, load failures: {}, impressions: {}, clicks: {}, closes: {}, rewards: {}, revenue: {} micros",
+            self.loads,
+            self.load_failures,
+            self.impressions,
+            self.clicks,
+            self.closes,
+            self.rewards_granted,
+            self.total_revenue_micros
+        )
+    }
+}
+
+/// Counters of all ad events written since startup or the last [`AdMetrics::reset`].
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource)]
+pub struct AdMetrics {
+    /// Counters of all ad types.
+    pub totals: AdCounts,
+    /// Counters of each ad type, ad types without events are missing.
+    pub per_type: HashMap<AdType, AdCounts>,
+}
+
+impl AdMetrics {
+    /// Counters of one ad type.
+    pub fn get(&self, ad_type: AdType) -> AdCounts {
+        self.per_type.get(&ad_type).copied().unwrap_or_default()
+    }
+
+    /// Set all counters back to zero.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl Display for AdMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "total: {}", self.totals)?;
+        for ad_type in [
+            AdType::Banner,
+            AdType::Interstitial,
+            AdType::Rewarded,
+            AdType::AppOpen,
+            AdType::Native,
+        ] {
+            if let Some(counts) = self.per_type.get(&ad_type) {
+                write!(f, "\n{ad_type}: {counts}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<AdMetrics>()
+        .init_resource::<AdMetrics>()
+        .add_systems(Update, update_metrics);
+}
+
+fn update_metrics(mut messages: MessageReader<AdMessage>, mut metrics: ResMut<AdMetrics>) {
+    for message in messages.read() {
+        metrics.totals.record(message);
+        if let Some(ad_type) = message.ad_type() {
+            metrics.per_type.entry(ad_type).or_default().record(message);
+        }
+    }
+}
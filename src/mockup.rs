@@ -1,33 +1,73 @@
 //! Mockup implementation of the AdManager trait.
 //! Implements the AdManager trait for testing purposes.
 
-use bevy_app::{App, PostStartup, Update};
-use bevy_derive::Deref;
+use accesskit::{Node as AccessKitNode, Role};
+use bevy_a11y::AccessibilityNode;
+use bevy_app::{App, Update};
+use bevy_asset::{AssetServer, Assets, Handle};
+use bevy_color::{Alpha, Color};
+use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     bundle::Bundle,
     children,
     component::Component,
     entity::Entity,
-    hierarchy::ChildOf,
-    lifecycle::Remove,
+    event::Event,
+    hierarchy::{ChildOf, Children},
+    lifecycle::{Add, Remove},
     observer::On,
     prelude::{ReflectComponent, ReflectResource},
-    query::With,
+    query::{Has, QueryData, With},
     resource::Resource,
     schedule::{IntoScheduleConfigs, common_conditions::resource_exists},
-    system::{Commands, In, Query, Res, ResMut, SystemParam},
+    spawn::{SpawnIter, SpawnRelated},
+    system::{Commands, EntityCommands, In, Local, Query, Res, ResMut, SystemParam},
+};
+use bevy_image::Image;
+use bevy_input::{ButtonInput, keyboard::KeyCode};
+use bevy_math::Vec2;
+use bevy_picking::{
+    Pickable,
+    events::{Click, Pointer},
 };
-use bevy_picking::events::{Click, Pointer};
 use bevy_reflect::Reflect;
-use bevy_time::{Time, Timer, TimerMode};
+use bevy_text::{TextColor, TextFont};
+use bevy_time::{Real, Time, Timer, TimerMode, Virtual};
 use bevy_ui::{
-    AlignItems, BackgroundColor, FlexDirection, JustifyContent, JustifyItems, Node, PositionType,
-    Val,
-    widget::{Button, ImageNode, Text},
+    AlignItems, AlignSelf, BackgroundColor, BackgroundGradient, BorderRadius, ComputedNode,
+    FlexDirection, JustifyContent, JustifyItems, LinearGradient, Node, Overflow, PositionType,
+    UiRect, UiTransform, Val, Val2,
+    widget::{Button, ImageNode, NodeImageMode, Text},
+};
+use bevy_window::{PrimaryWindow, Window};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, Hasher, RandomState},
+    panic::AssertUnwindSafe,
+    time::Duration,
+};
+
+#[cfg(feature = "audio")]
+mod audio;
+#[cfg(feature = "audio")]
+pub use audio::MockupAudioCues;
+#[cfg(feature = "config_asset")]
+pub(crate) mod config;
+mod event_log;
+#[cfg(feature = "config_asset")]
+pub use config::{
+    AdDisplayConfig, MockupAdsConfig, MockupAdsConfigHandle, MockupAdsConfigLoader,
+    MockupAdsConfigLoaderError,
 };
-use std::time::Duration;
+pub use event_log::AdEventLog;
 
-use crate::{AdManager, AdMessage, AdType};
+use crate::{
+    AdCloseReason, AdError, AdEventQueue, AdLoadRetry, AdManager, AdMessage, AdRetryState, AdType,
+    AdsEnabled, BannerPosition, BannerSize, ConsentState, ConsentStatus, FrequencyCap,
+    FrequencyCapSession, Reward,
+};
 
 #[derive(Debug, Resource, Reflect)]
 #[reflect(Resource)]
@@ -35,113 +75,847 @@ pub struct MockupAds {
     pub initialized: bool,
     pub rewarded: AdDisplaySettings,
     pub interstitial: AdDisplaySettings,
+    pub app_open: AdDisplaySettings,
+    pub native: NativeAdLayout,
+    pub banner_size: BannerSize,
+    pub banner_position: BannerPosition,
+    #[cfg(feature = "audio")]
+    pub audio_cues: MockupAudioCues,
     pub rewarded_ad_reward: Reward,
+    /// Rewards granted alongside `rewarded_ad_reward`, each with its own
+    /// `RewardedAdEarnedReward`. Chained rewarded ads don't grant them again.
+    pub extra_rewards: Vec<Reward>,
+    /// Load time of ad types without a specific one.
     pub loading_time_ms: u64,
+    /// Load times vary randomly by up to this much, ignored with a [`MockupAds::waterfall`].
+    pub load_jitter_ms: u64,
+    /// Load time of interstitial ads, `loading_time_ms` when `None`.
+    pub interstitial_load_ms: Option<u64>,
+    /// Load time of rewarded ads, `loading_time_ms` when `None`.
+    pub rewarded_load_ms: Option<u64>,
+    pub consent: ConsentState,
+    /// Multiplier applied to the previous reward by a chained rewarded ad.
+    pub chain_multiplier: i32,
+    /// Last earned reward that can still be multiplied by a chained rewarded ad.
+    pub chainable_reward: Option<Reward>,
+    /// Simulated mediation waterfall tried in order on every load.
+    /// When empty, loads always fill after `loading_time_ms`.
+    pub waterfall: Vec<MockupAdNetwork>,
+    /// Seed for the mockup simulations, random when `None`.
+    pub rng_seed: Option<u64>,
+    /// Region the mockup pretends the user is in.
+    pub simulated_region: String,
+    /// Whether consent is required in a region, regions not listed don't require it.
+    pub consent_regions: HashMap<String, bool>,
+    /// Text displayed instead of an [`AdDisplay::Image`] whose image failed to load.
+    pub image_fallback_text: String,
+    /// Mean eCPM in micros of USD used for the fake revenue of closed fullscreen ads.
+    pub fake_ecpm_micros: i64,
+    /// Whether a displayed banner counts for [`any_ad_showing`]/[`no_ad_showing`].
+    pub banners_count_as_showing: bool,
+    /// Simulated connectivity, while false loads and consent requests fail right away
+    /// with [`AdError::NetworkError`].
+    pub network_available: bool,
+    /// Close the displayed fullscreen ad with the Escape key once its close button is shown,
+    /// like clicking the close button.
+    pub close_on_escape: bool,
+    /// Skip the first interstitial show, e.g. to keep the onboarding ad free.
+    /// Cleared once an interstitial has been skipped.
+    pub skip_first_interstitial: bool,
+    /// Interstitials are throttled for this long after a rewarded ad closed.
+    pub post_rewarded_cooldown: Duration,
+    /// Elapsed [`Time`] until which interstitials are throttled after the last rewarded ad closed.
+    pub rewarded_cooldown_until: Option<Duration>,
+    /// Maximum number of ads of each type loaded ahead, each load call preloads
+    /// another one until the limit is reached.
+    pub max_preloaded: usize,
+    /// Z-index of the displayed ads, game UI has to be above it to cover them.
+    pub z_index: i32,
+    /// Whether the banner can be tapped, when false taps pass through to the game.
+    pub banner_clickable: bool,
+    /// Simulate the player returning from the browser opened by a banner click,
+    /// emitting `AdClosed` for the banner this long after the click.
+    /// The banner itself stays displayed.
+    pub banner_click_through_ms: Option<u64>,
+    pub banner_animations: AdAnimations,
+    /// Appearance of the close button of fullscreen ads.
+    pub close_button: CloseButtonStyle,
+    /// Appearance of the time left countdown of fullscreen ads.
+    pub time_left: TimeLeftStyle,
+    /// Displayed banners are refreshed with a new `AdLoaded` every interval.
+    pub banner_refresh_interval: Option<Duration>,
+    /// Last value applied with [`AdManager::set_muted`], fullscreen ads show a muted icon.
+    pub muted: bool,
+    /// Last value applied with [`AdManager::set_ad_volume`].
+    pub volume: f32,
+    /// Network reported as filling ads when no [`MockupAds::waterfall`] is configured.
+    pub fake_network: String,
+    /// The next load of this ad type fails with [`MockupAds::load_error`].
+    /// Cleared once the failing load started.
+    pub fail_next_load: Option<AdType>,
+    pub load_error: AdError,
+    /// Status of the simulated consent flow, see [`AdManager::request_consent`].
+    pub consent_status: ConsentStatus,
+    /// Time the simulated consent form takes to complete.
+    pub consent_form_ms: u64,
+    /// Consent given by the user in the simulated consent form.
+    pub consent_form_result: ConsentState,
+    /// Whether the simulated consent form fails instead.
+    pub consent_form_fails: bool,
+}
+
+/// Builder for [`MockupAds`], see [`MockupAds::builder`].
+#[derive(Debug, Default)]
+pub struct MockupAdsBuilder(MockupAds);
+
+impl MockupAdsBuilder {
+    pub fn loading_time_ms(mut self, loading_time_ms: u64) -> Self {
+        self.0.loading_time_ms = loading_time_ms;
+        self
+    }
+    pub fn load_jitter_ms(mut self, load_jitter_ms: u64) -> Self {
+        self.0.load_jitter_ms = load_jitter_ms;
+        self
+    }
+    pub fn rewarded_display(mut self, display: AdDisplay) -> Self {
+        self.0.rewarded.display = display;
+        self
+    }
+    pub fn interstitial_auto_close(mut self, auto_close: bool) -> Self {
+        self.0.interstitial.auto_close = auto_close;
+        self
+    }
+    /// Reward granted by rewarded ads.
+    pub fn reward(mut self, amount: i32, type_name: impl Into<String>) -> Self {
+        self.0.rewarded_ad_reward = Reward {
+            amount,
+            type_name: type_name.into(),
+        };
+        self
+    }
+    /// Additional reward granted by rewarded ads, e.g. gems next to coins.
+    pub fn extra_reward(mut self, amount: i32, type_name: impl Into<String>) -> Self {
+        self.0.extra_rewards.push(Reward {
+            amount,
+            type_name: type_name.into(),
+        });
+        self
+    }
+    /// Display duration of a fullscreen ad type, ignored for banner and native ads.
+    pub fn duration_ms_for(mut self, ad_type: AdType, duration_ms: u64) -> Self {
+        match ad_type {
+            AdType::Interstitial => self.0.interstitial.duration_ms = duration_ms,
+            AdType::Rewarded => self.0.rewarded.duration_ms = duration_ms,
+            AdType::AppOpen => self.0.app_open.duration_ms = duration_ms,
+            AdType::Banner | AdType::Native => {}
+        }
+        self
+    }
+    /// Throttle interstitials for this long after a rewarded ad closed.
+    pub fn post_rewarded_cooldown(mut self, cooldown: Duration) -> Self {
+        self.0.post_rewarded_cooldown = cooldown;
+        self
+    }
+    /// Seed the mockup simulations, e.g. the waterfall, for reproducible runs.
+    pub fn rng_seed(mut self, seed: u64) -> Self {
+        self.0.rng_seed = Some(seed);
+        self
+    }
+    /// Append a network to the simulated mediation waterfall.
+    pub fn network(
+        mut self,
+        name: impl Into<String>,
+        fill_probability: f32,
+        latency_ms: u64,
+    ) -> Self {
+        self.0.waterfall.push(MockupAdNetwork {
+            name: name.into(),
+            fill_probability,
+            latency_ms,
+        });
+        self
+    }
+    pub fn build(self) -> MockupAds {
+        self.0
+    }
+}
+
+/// Session state of the mockup that can be saved and restored, see
+/// [`MockupAdsSystem::snapshot`]. Live ad entities are not part of the snapshot.
+/// Cooldowns are saved as the time left, so they carry over into a restarted app.
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub struct MockupSnapshot {
+    pub consent: ConsentState,
+    pub consent_status: ConsentStatus,
+    pub chainable_reward: Option<Reward>,
+    pub skip_first_interstitial: bool,
+    /// Time left of the [`MockupAds::post_rewarded_cooldown`], `None` once it is over.
+    pub rewarded_cooldown_left: Option<Duration>,
+    /// Triggers counted towards the next interstitial, see [`InterstitialPacing::count`].
+    pub interstitial_pacing_count: u32,
+    /// Shows recorded by the [`FrequencyCap`], `None` without one.
+    pub frequency_cap: Option<FrequencyCapSession>,
+    pub ads_enabled: bool,
+}
+
+impl MockupAds {
+    /// Start building the mockup configuration from the defaults.
+    /// Insert the built resource before adding the plugin so it isn't overwritten.
+    pub fn builder() -> MockupAdsBuilder {
+        MockupAdsBuilder::default()
+    }
+
+    /// Combined reward of a chained rewarded ad, the last reward multiplied by
+    /// `chain_multiplier`, if it can still be chained. It replaces the last reward.
+    pub fn chained_reward(&self) -> Option<Reward> {
+        self.chainable_reward.as_ref().map(|previous| Reward {
+            amount: previous.amount.saturating_mul(self.chain_multiplier),
+            type_name: previous.type_name.clone(),
+        })
+    }
 }
 
 #[derive(Debug, Reflect, Resource, Default)]
 #[reflect(Resource)]
 pub struct MockupFakeLoader {
     duration: Duration,
-    rewarded: Option<Timer>,
-    interstitial: Option<Timer>,
+    /// Per ad type overrides of `duration`.
+    durations: HashMap<AdType, Duration>,
+    /// Loads per ad type, oldest first.
+    rewarded: Vec<FakeLoad>,
+    interstitial: Vec<FakeLoad>,
+    app_open: Vec<FakeLoad>,
+    native: Vec<FakeLoad>,
+    /// Maximum number of loaded or loading ads per ad type, see [`MockupAds::max_preloaded`].
+    max_preloaded: usize,
+    /// Whether new loads are personalized, see [`ConsentState::personalized_ads`].
+    personalized: bool,
+    /// Ad unit id of the last banner load.
+    banner_ad_id: String,
+    /// Displayed banner, until it is despawned. Banners aren't tracked by loads.
+    banner: Option<Entity>,
+    /// Displayed native ad, until it is despawned.
+    native_ad: Option<Entity>,
+    /// Displayed fullscreen ad and its type, until it is despawned.
+    fullscreen: Option<(Entity, AdType)>,
+    /// Whether `fullscreen` was shown but its `AdOpened` is not written yet.
+    /// Until then the show can still be cancelled with [`MockupAdsSystem::skip_pending_ad`].
+    opening: bool,
+}
+
+/// Ad load in progress, resolved once its timer finishes.
+#[derive(Debug, Reflect)]
+struct FakeLoad {
+    timer: Timer,
+    /// Ad unit id passed to the load.
+    ad_id: String,
+    /// Network that filled the ad, `None` when no network in the waterfall did.
+    network: Option<String>,
+    /// Error the load fails with, `None` when it succeeds.
+    error: Option<AdError>,
+    personalized: bool,
+}
+
+impl FakeLoad {
+    fn is_loaded(&self) -> bool {
+        self.error.is_none() && self.timer.is_finished()
+    }
+}
+
+/// Read-only view of the mockup timers, see [`MockupAdsSystem::timers`].
+/// Each field is the remaining load time, `None` when no load of that ad type is in flight.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub struct MockupTimers {
+    pub rewarded_remaining: Option<Duration>,
+    pub interstitial_remaining: Option<Duration>,
+    pub app_open_remaining: Option<Duration>,
+    pub native_remaining: Option<Duration>,
 }
 
 impl MockupFakeLoader {
     pub fn set_duration(&mut self, duration: Duration) {
         self.duration = duration;
-        self.interstitial = None;
-        self.rewarded = None;
+        self.interstitial.clear();
+        self.rewarded.clear();
+        self.app_open.clear();
+        self.native.clear();
+    }
+    /// Set the load duration of one ad type, overriding the one set with `set_duration`.
+    pub fn set_duration_for(&mut self, ad_type: AdType, duration: Duration) {
+        self.durations.insert(ad_type, duration);
+    }
+    /// Load duration of this ad type.
+    pub fn duration_for(&self, ad_type: AdType) -> Duration {
+        self.durations
+            .get(&ad_type)
+            .copied()
+            .unwrap_or(self.duration)
     }
     pub fn is_loaded(&self, ad_type: AdType) -> bool {
-        match ad_type {
-            AdType::Rewarded => self
-                .rewarded
-                .as_ref()
-                .is_some_and(|timer| timer.is_finished()),
-            AdType::Interstitial => self
-                .interstitial
-                .as_ref()
-                .is_some_and(|timer| timer.is_finished()),
-            _ => true,
-        }
-    }
-    pub fn start_load(&mut self, ad_type: AdType) {
-        match ad_type {
-            AdType::Rewarded => {
-                self.rewarded = Some(Timer::new(self.duration, TimerMode::Once));
-            }
-            AdType::Interstitial => {
-                self.interstitial = Some(Timer::new(self.duration, TimerMode::Once));
-            }
-            _ => {}
+        self.loaded_count(ad_type) > 0
+    }
+    /// Number of loaded ads of this type ready to be shown.
+    pub fn loaded_count(&self, ad_type: AdType) -> usize {
+        match self.slot(ad_type) {
+            Some(slot) => slot.iter().filter(|load| load.is_loaded()).count(),
+            None => 1,
+        }
+    }
+    /// Network that filled the next loaded ad of this type.
+    pub fn loaded_network(&self, ad_type: AdType) -> Option<String> {
+        self.slot(ad_type)?
+            .iter()
+            .find(|load| load.is_loaded())
+            .and_then(|load| load.network.clone())
+    }
+    /// Ad unit id of the next loaded ad of this type, or of the oldest load in flight.
+    pub fn ad_id(&self, ad_type: AdType) -> Option<&str> {
+        if ad_type == AdType::Banner {
+            return Some(&self.banner_ad_id);
+        }
+        let slot = self.slot(ad_type)?;
+        slot.iter()
+            .find(|load| load.is_loaded())
+            .or(slot.first())
+            .map(|load| load.ad_id.as_str())
+    }
+    /// Is a load of this ad type in flight?
+    pub fn is_loading(&self, ad_type: AdType) -> bool {
+        self.remaining(ad_type).is_some()
+    }
+    /// Remaining time of the in-flight load of this ad type finishing first.
+    pub fn remaining(&self, ad_type: AdType) -> Option<Duration> {
+        self.slot(ad_type)?
+            .iter()
+            .filter(|load| !load.timer.is_finished())
+            .map(|load| load.timer.remaining())
+            .min()
+    }
+    /// Can another load of this ad type be started?
+    /// Once [`MockupAds::max_preloaded`] ads are loaded or loading, a new load replaces
+    /// the oldest loaded ad, and is refused while all of them are still loading.
+    pub fn can_start_load(&self, ad_type: AdType) -> bool {
+        self.slot(ad_type).is_some_and(|slot| {
+            slot.len() < self.max_preloaded.max(1) || slot.iter().any(FakeLoad::is_loaded)
+        })
+    }
+    /// Start loading an ad.
+    /// Returns false if no more loads of this ad type can be started, see
+    /// [`MockupFakeLoader::can_start_load`].
+    pub fn start_load(&mut self, ad_type: AdType) -> bool {
+        let duration = self.duration_for(ad_type);
+        self.start_load_with(ad_type, "", duration, None, None)
+    }
+    fn start_load_with(
+        &mut self,
+        ad_type: AdType,
+        ad_id: &str,
+        duration: Duration,
+        network: Option<String>,
+        error: Option<AdError>,
+    ) -> bool {
+        if !self.can_start_load(ad_type) {
+            return false;
+        }
+        let max_preloaded = self.max_preloaded.max(1);
+        let personalized = self.personalized;
+        let Some(slot) = self.slot_mut(ad_type) else {
+            return false;
+        };
+        if slot.len() >= max_preloaded
+            && let Some(oldest_loaded) = slot.iter().position(FakeLoad::is_loaded)
+        {
+            slot.remove(oldest_loaded);
         }
+        slot.push(FakeLoad {
+            timer: Timer::new(duration, TimerMode::Once),
+            ad_id: ad_id.to_string(),
+            network,
+            error,
+            personalized,
+        });
+        true
     }
+    /// Discard all loads of this ad type.
     fn reset(&mut self, ad_type: AdType) {
+        if let Some(slot) = self.slot_mut(ad_type) {
+            slot.clear();
+        }
+    }
+    /// Use up the next loaded ad of this type, leaving the other ones ready.
+    fn consume(&mut self, ad_type: AdType) {
+        let Some(slot) = self.slot_mut(ad_type) else {
+            return;
+        };
+        if let Some(loaded) = slot.iter().position(FakeLoad::is_loaded) {
+            slot.remove(loaded);
+        }
+    }
+    fn slot(&self, ad_type: AdType) -> Option<&Vec<FakeLoad>> {
         match ad_type {
-            AdType::Rewarded => {
-                self.rewarded = None;
-            }
-            AdType::Interstitial => {
-                self.interstitial = None;
-            }
-            _ => {}
+            AdType::Rewarded => Some(&self.rewarded),
+            AdType::Interstitial => Some(&self.interstitial),
+            AdType::AppOpen => Some(&self.app_open),
+            AdType::Native => Some(&self.native),
+            AdType::Banner => None,
         }
     }
-    fn update(mut loader: ResMut<MockupFakeLoader>, time: Res<Time>) {
-        if let Some(ref mut timer) = loader.rewarded {
-            timer.tick(time.delta());
-            if timer.just_finished() {
-                crate::write_event_to_queue(AdMessage::AdLoaded {
-                    ad_type: AdType::Rewarded,
-                });
-            }
+    fn slot_mut(&mut self, ad_type: AdType) -> Option<&mut Vec<FakeLoad>> {
+        match ad_type {
+            AdType::Rewarded => Some(&mut self.rewarded),
+            AdType::Interstitial => Some(&mut self.interstitial),
+            AdType::AppOpen => Some(&mut self.app_open),
+            AdType::Native => Some(&mut self.native),
+            AdType::Banner => None,
         }
-        if let Some(ref mut timer) = loader.interstitial {
-            timer.tick(time.delta());
-            if timer.just_finished() {
-                crate::write_event_to_queue(AdMessage::AdLoaded {
-                    ad_type: AdType::Interstitial,
-                });
-            }
+    }
+    fn update(
+        mut loader: ResMut<MockupFakeLoader>,
+        time: Res<Time>,
+        queue: Res<AdEventQueue>,
+        #[cfg(feature = "diagnostics")] mut diagnostics: bevy_diagnostic::Diagnostics,
+    ) {
+        #[cfg(feature = "diagnostics")]
+        let start = bevy_platform::time::Instant::now();
+        for ad_type in [
+            AdType::Rewarded,
+            AdType::Interstitial,
+            AdType::AppOpen,
+            AdType::Native,
+        ] {
+            let Some(slot) = loader.slot_mut(ad_type) else {
+                continue;
+            };
+            slot.retain_mut(|load| {
+                load.timer.tick(time.delta());
+                if !load.timer.just_finished() {
+                    return true;
+                }
+                match load.error.take() {
+                    None => {
+                        queue.write(AdMessage::AdLoaded {
+                            ad_type,
+                            ad_id: load.ad_id.clone(),
+                            network: load.network.clone(),
+                            personalized: load.personalized,
+                        });
+                        true
+                    }
+                    Some(error) => {
+                        queue.write(AdMessage::AdFailedToLoad {
+                            ad_type,
+                            ad_id: std::mem::take(&mut load.ad_id),
+                            error,
+                        });
+                        false
+                    }
+                }
+            });
+        }
+        #[cfg(feature = "diagnostics")]
+        diagnostics.add_measurement(&crate::diagnostics::MOCKUP_FAKE_LOADER, || {
+            start.elapsed().as_secs_f64() * 1000.0
+        });
+    }
+}
+
+/// Simulated consent form in progress, resolved once its timer finishes.
+#[derive(Debug, Reflect, Resource, Default)]
+#[reflect(Resource)]
+pub struct MockupConsentForm(Option<Timer>);
+
+impl MockupConsentForm {
+    /// Is the consent form displayed?
+    pub fn is_pending(&self) -> bool {
+        self.0.is_some()
+    }
+    fn update(
+        mut form: ResMut<MockupConsentForm>,
+        mut cfg: ResMut<MockupAds>,
+        time: Res<Time>,
+        queue: Res<AdEventQueue>,
+    ) {
+        let Some(timer) = &mut form.0 else {
+            return;
+        };
+        if !timer.tick(time.delta()).just_finished() {
+            return;
+        }
+        form.0 = None;
+        if cfg.consent_form_fails {
+            queue.write(AdMessage::ConsentGathered {
+                success: false,
+                error: Some(AdError::Internal(
+                    "Simulated consent form failure".to_string(),
+                )),
+                state: cfg.consent,
+            });
+            return;
+        }
+        cfg.consent = cfg.consent_form_result;
+        cfg.consent_status = ConsentStatus::Obtained;
+        queue.write(AdMessage::ConsentGathered {
+            success: true,
+            error: None,
+            state: cfg.consent,
+        });
+    }
+}
+
+/// Fake ad network tried by the mockup mediation waterfall.
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct MockupAdNetwork {
+    pub name: String,
+    /// Chance in `0.0..=1.0` that the network fills the ad request.
+    pub fill_probability: f32,
+    /// Time it takes the network to respond.
+    pub latency_ms: u64,
+}
+
+/// Called with every fullscreen ad entity right after it is spawned, e.g. to insert
+/// a `Name` or analytics components. The ad is still shown if the hook panics.
+#[derive(Resource, Clone, Copy)]
+pub struct MockupAdSpawnHook(pub fn(&mut EntityCommands, AdType));
+
+/// Random number generator used by the mockup simulations.
+/// Seeded from [`MockupAds::rng_seed`] on initialization.
+#[derive(Resource, Deref, DerefMut)]
+pub struct MockupRng(StdRng);
+
+impl Default for MockupRng {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl MockupRng {
+    pub fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| RandomState::new().build_hasher().finish());
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+/// Is the image loaded or still loading? Missing and failed images can't be displayed.
+fn image_available(
+    handle: &Handle<Image>,
+    images: Option<&Assets<Image>>,
+    asset_server: Option<&AssetServer>,
+) -> bool {
+    if images.is_some_and(|images| images.contains(handle.id())) {
+        return true;
+    }
+    asset_server
+        .and_then(|server| server.get_load_state(handle.id()))
+        .is_some_and(|state| state.is_loading())
+}
+
+/// Walk the waterfall in order, returning the time spent and the network that filled the ad.
+fn run_waterfall(waterfall: &[MockupAdNetwork], rng: &mut StdRng) -> (Duration, Option<String>) {
+    let mut latency = Duration::ZERO;
+    for network in waterfall {
+        latency += Duration::from_millis(network.latency_ms);
+        if rng.random::<f32>() < network.fill_probability {
+            return (latency, Some(network.name.clone()));
         }
     }
+    (latency, None)
 }
 
 #[derive(Debug, Reflect, Clone)]
 pub struct AdDisplaySettings {
     pub display: AdDisplay,
     pub show_time_left: bool,
+    /// Show a bar along the bottom edge filling up while the ad plays.
+    pub show_progress_bar: bool,
     pub auto_close: bool,
+    /// Rewarded ads only: close right after granting the reward instead of showing
+    /// an end card with a close button.
+    pub auto_close_after_reward: bool,
+    /// Rewarded ads only: ask the player to opt in before playing the ad.
+    pub opt_in_prompt: bool,
     pub duration_ms: u64,
+    pub animations: AdAnimations,
+    /// Show a skip button after this time, skipping closes the ad without a reward.
+    pub skip_after_ms: Option<u64>,
+    /// Show the close button after this time, independently of `duration_ms`.
+    /// Values of `duration_ms` or more show it only once the ad completed, which is
+    /// the default. Ignored by auto-closing ads.
+    pub closeable_after_ms: u64,
+    /// Full screen overlay drawn behind the ad, e.g. to dim the game behind translucent ads.
+    pub scrim: Option<BackgroundColor>,
+    /// Rewarded ads only: reward granted when the ad is closed before it completed,
+    /// see [`AdDisplaySettings::closeable_after_ms`].
+    pub early_close_reward: EarlyCloseReward,
+}
+
+/// Reward granted for a rewarded ad closed before it completed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum EarlyCloseReward {
+    /// No reward.
+    #[default]
+    None,
+    /// The full reward.
+    Full,
+    /// The reward amount scaled by the watched fraction of the ad, rounded down.
+    Prorated,
 }
 
 impl Default for AdDisplaySettings {
     fn default() -> Self {
         Self {
-            display: AdDisplay::SolidBackgroundWithText(
-                BackgroundColor(bevy_color::palettes::tailwind::ZINC_500.into()),
-                "Displaying an ad".to_string(),
-            ),
+            display: AdDisplay::SolidBackgroundWithDefaultText(BackgroundColor(
+                bevy_color::palettes::tailwind::ZINC_500.into(),
+            )),
             show_time_left: true,
+            show_progress_bar: false,
             auto_close: false,
+            auto_close_after_reward: false,
+            opt_in_prompt: false,
             duration_ms: 3500,
+            animations: AdAnimations::default(),
+            skip_after_ms: None,
+            closeable_after_ms: u64::MAX,
+            scrim: None,
+            early_close_reward: EarlyCloseReward::None,
+        }
+    }
+}
+
+impl AdDisplaySettings {
+    /// Start building settings from the defaults.
+    pub fn builder() -> AdDisplaySettingsBuilder {
+        AdDisplaySettingsBuilder::default()
+    }
+}
+
+/// Builder for [`AdDisplaySettings`], see [`AdDisplaySettings::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct AdDisplaySettingsBuilder(AdDisplaySettings);
+
+impl AdDisplaySettingsBuilder {
+    pub fn display(mut self, display: AdDisplay) -> Self {
+        self.0.display = display;
+        self
+    }
+    pub fn show_time_left(mut self, show_time_left: bool) -> Self {
+        self.0.show_time_left = show_time_left;
+        self
+    }
+    pub fn show_progress_bar(mut self, show_progress_bar: bool) -> Self {
+        self.0.show_progress_bar = show_progress_bar;
+        self
+    }
+    pub fn auto_close(mut self, auto_close: bool) -> Self {
+        self.0.auto_close = auto_close;
+        self
+    }
+    pub fn auto_close_after_reward(mut self, auto_close_after_reward: bool) -> Self {
+        self.0.auto_close_after_reward = auto_close_after_reward;
+        self
+    }
+    pub fn opt_in_prompt(mut self, opt_in_prompt: bool) -> Self {
+        self.0.opt_in_prompt = opt_in_prompt;
+        self
+    }
+    pub fn duration_ms(mut self, duration_ms: u64) -> Self {
+        self.0.duration_ms = duration_ms;
+        self
+    }
+    pub fn animations(mut self, animations: AdAnimations) -> Self {
+        self.0.animations = animations;
+        self
+    }
+    pub fn skip_after_ms(mut self, skip_after_ms: Option<u64>) -> Self {
+        self.0.skip_after_ms = skip_after_ms;
+        self
+    }
+    pub fn closeable_after_ms(mut self, closeable_after_ms: u64) -> Self {
+        self.0.closeable_after_ms = closeable_after_ms;
+        self
+    }
+    pub fn scrim(mut self, scrim: Option<BackgroundColor>) -> Self {
+        self.0.scrim = scrim;
+        self
+    }
+    pub fn early_close_reward(mut self, early_close_reward: EarlyCloseReward) -> Self {
+        self.0.early_close_reward = early_close_reward;
+        self
+    }
+    pub fn build(self) -> AdDisplaySettings {
+        self.0
+    }
+}
+
+/// Animation played when an ad appears or disappears.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum ShowAnimation {
+    #[default]
+    None,
+    Fade,
+    /// Moves up, entering from below or leaving through the top.
+    SlideUp,
+    /// Moves down, entering from above or leaving through the bottom.
+    SlideDown,
+    Scale,
+}
+
+/// Enter and exit animations of an ad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub struct AdAnimations {
+    pub enter: ShowAnimation,
+    pub exit: ShowAnimation,
+    pub duration_ms: u64,
+}
+
+impl Default for AdAnimations {
+    fn default() -> Self {
+        Self {
+            enter: ShowAnimation::None,
+            exit: ShowAnimation::None,
+            duration_ms: 300,
+        }
+    }
+}
+
+/// Texts displayed by the mockup ads, replace it to localize them.
+#[derive(Debug, Clone, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct MockupAdStrings {
+    /// Body of [`AdDisplay::SolidBackgroundWithDefaultText`] ads.
+    pub ad_body: String,
+    /// Countdown of ads showing the time left, `{seconds}` is replaced with the time left
+    /// formatted according to [`TimeLeftStyle::format`].
+    pub time_left: String,
+    /// Question of the rewarded ad opt-in prompt, `{amount}` and `{reward}` are replaced
+    /// with the reward amount and type.
+    pub opt_in_prompt: String,
+    pub opt_in_accept: String,
+    pub opt_in_decline: String,
+    /// Label displayed on ads while [`MockupAds::muted`].
+    pub muted: String,
+    pub skip: String,
+}
+
+impl Default for MockupAdStrings {
+    fn default() -> Self {
+        Self {
+            ad_body: "Displaying an ad".to_string(),
+            time_left: "{seconds}s left".to_string(),
+            opt_in_prompt: "Watch ad for {amount} {reward}?".to_string(),
+            opt_in_accept: "Yes".to_string(),
+            opt_in_decline: "No".to_string(),
+            muted: "Muted".to_string(),
+            skip: "Skip".to_string(),
+        }
+    }
+}
+
+impl MockupAdStrings {
+    fn format_time_left(&self, seconds: f32, format: TimeLeftFormat) -> String {
+        self.time_left.replace("{seconds}", &format.format(seconds))
+    }
+    fn format_opt_in_prompt(&self, reward: &Reward) -> String {
+        self.opt_in_prompt
+            .replace("{amount}", &reward.amount.to_string())
+            .replace("{reward}", &reward.type_name)
+    }
+}
+
+/// Appearance of the time left countdown of fullscreen ads.
+#[derive(Debug, Clone, Reflect)]
+pub struct TimeLeftStyle {
+    /// Corner of the ad the countdown is displayed in, `Top` and `Bottom` center it.
+    pub anchor: BannerPosition,
+    pub font_size: f32,
+    pub color: Color,
+    pub format: TimeLeftFormat,
+}
+
+impl Default for TimeLeftStyle {
+    fn default() -> Self {
+        Self {
+            anchor: BannerPosition::TopLeft,
+            font_size: 20.0,
+            color: Color::WHITE,
+            format: TimeLeftFormat::default(),
+        }
+    }
+}
+
+/// How the time left of [`TimeLeftStyle`] is formatted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum TimeLeftFormat {
+    /// Whole seconds, e.g. `3`.
+    Seconds,
+    /// Seconds with tenths, e.g. `3.2`.
+    SecondsTenths,
+    /// Seconds with hundredths, e.g. `3.25`.
+    #[default]
+    SecondsHundredths,
+    /// Minutes and seconds, e.g. `0:03`.
+    MmSs,
+}
+
+impl TimeLeftFormat {
+    fn format(self, seconds: f32) -> String {
+        match self {
+            TimeLeftFormat::Seconds => format!("{}", seconds.ceil() as u32),
+            TimeLeftFormat::SecondsTenths => format!("{seconds:.1}"),
+            TimeLeftFormat::SecondsHundredths => format!("{seconds:.2}"),
+            TimeLeftFormat::MmSs => {
+                let seconds = seconds.ceil() as u32;
+                format!("{}:{:02}", seconds / 60, seconds % 60)
+            }
+        }
+    }
+}
+
+/// Appearance of the close button of fullscreen ads.
+#[derive(Debug, Clone, Reflect)]
+pub struct CloseButtonStyle {
+    /// Width and height in pixels.
+    pub size: f32,
+    pub color: Color,
+    pub corner_radius: f32,
+    /// Text displayed on the button, e.g. "✕".
+    pub label: Option<String>,
+    /// Label announced by screen readers.
+    pub accessibility_label: String,
+}
+
+impl Default for CloseButtonStyle {
+    fn default() -> Self {
+        Self {
+            size: 30.0,
+            color: bevy_color::palettes::tailwind::RED_400.into(),
+            corner_radius: 0.0,
+            label: None,
+            accessibility_label: "Close ad".to_string(),
         }
     }
 }
 
-/// Reward for displaying an rewarded ad.
+/// Layout of a native ad, which is displayed as a bounded node
+/// that doesn't block pointer input outside of it.
 #[derive(Debug, Reflect, Clone)]
-pub struct Reward {
-    pub amount: i32,
-    pub type_name: String,
+pub struct NativeAdLayout {
+    pub width: f32,
+    pub height: f32,
+    pub headline: String,
+    pub body: String,
+    pub call_to_action: String,
+    pub background: BackgroundColor,
 }
 
-impl Default for Reward {
+impl Default for NativeAdLayout {
     fn default() -> Self {
         Self {
-            amount: 1,
-            type_name: "default".to_string(),
+            width: 320.0,
+            height: 120.0,
+            headline: "Native ad".to_string(),
+            body: "Displaying a native ad".to_string(),
+            call_to_action: "Install".to_string(),
+            background: BackgroundColor(bevy_color::palettes::tailwind::ZINC_500.into()),
         }
     }
 }
@@ -153,8 +927,64 @@ pub enum AdDisplay {
     SolidBackground(BackgroundColor),
     /// Display a fullscreen ad with a solid background color and a text message.
     SolidBackgroundWithText(BackgroundColor, String),
+    /// Display a fullscreen ad with a solid background color and [`MockupAdStrings::ad_body`].
+    SolidBackgroundWithDefaultText(BackgroundColor),
+    /// Display a fullscreen ad with a linear gradient background.
+    Gradient {
+        from: Color,
+        to: Color,
+        direction: GradientDirection,
+    },
     /// Display a fullscreen ad with an image.
-    Image(bevy_asset::Handle<bevy_image::Image>),
+    /// Falls back to [`MockupAds::image_fallback_text`] when the image failed to load.
+    Image {
+        handle: Handle<Image>,
+        fit: ImageFit,
+    },
+}
+
+impl AdDisplay {
+    /// Display an image covering the screen.
+    pub fn image(handle: Handle<Image>) -> Self {
+        AdDisplay::Image {
+            handle,
+            fit: ImageFit::default(),
+        }
+    }
+}
+
+/// How an [`AdDisplay::Image`] fills the screen.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum ImageFit {
+    /// Fill the screen ignoring the aspect ratio.
+    Stretch,
+    /// Fit the whole image on screen, letterboxed with black bars.
+    Contain,
+    /// Fill the screen keeping the aspect ratio, cropping the image.
+    #[default]
+    Cover,
+}
+
+/// Direction of an [`AdDisplay::Gradient`], from its `from` color to its `to` color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum GradientDirection {
+    /// From top to bottom.
+    #[default]
+    Vertical,
+    /// From left to right.
+    Horizontal,
+    /// From the top left to the bottom right corner.
+    Diagonal,
+}
+
+impl GradientDirection {
+    fn angle(self) -> f32 {
+        match self {
+            GradientDirection::Vertical => LinearGradient::TO_BOTTOM,
+            GradientDirection::Horizontal => LinearGradient::TO_RIGHT,
+            GradientDirection::Diagonal => LinearGradient::TO_BOTTOM_RIGHT,
+        }
+    }
 }
 
 impl Default for MockupAds {
@@ -163,8 +993,59 @@ impl Default for MockupAds {
             initialized: false,
             interstitial: AdDisplaySettings::default(),
             rewarded: AdDisplaySettings::default(),
+            app_open: AdDisplaySettings::default(),
+            native: NativeAdLayout::default(),
+            banner_size: BannerSize::default(),
+            banner_position: BannerPosition::default(),
+            #[cfg(feature = "audio")]
+            audio_cues: MockupAudioCues::default(),
             rewarded_ad_reward: Reward::default(),
+            extra_rewards: Vec::new(),
             loading_time_ms: 1000,
+            interstitial_load_ms: None,
+            rewarded_load_ms: None,
+            consent: ConsentState::default(),
+            chain_multiplier: 2,
+            chainable_reward: None,
+            waterfall: Vec::new(),
+            rng_seed: None,
+            load_jitter_ms: 0,
+            image_fallback_text: "Ad creative failed to load".to_string(),
+            fake_ecpm_micros: 5_000_000,
+            banners_count_as_showing: false,
+            skip_first_interstitial: false,
+            network_available: true,
+            close_on_escape: false,
+            post_rewarded_cooldown: Duration::ZERO,
+            rewarded_cooldown_until: None,
+            max_preloaded: 1,
+            z_index: 500,
+            banner_clickable: true,
+            banner_click_through_ms: None,
+            close_button: CloseButtonStyle::default(),
+            time_left: TimeLeftStyle::default(),
+            banner_animations: AdAnimations::default(),
+            banner_refresh_interval: None,
+            muted: false,
+            volume: 1.0,
+            fake_network: "mockup".to_string(),
+            fail_next_load: None,
+            load_error: AdError::NoFill,
+            consent_status: ConsentStatus::Unknown,
+            consent_form_ms: 500,
+            consent_form_result: ConsentState {
+                storage: true,
+                analytics: true,
+                personalized_ads: true,
+            },
+            consent_form_fails: false,
+            simulated_region: "EEA".to_string(),
+            consent_regions: HashMap::from([
+                ("EEA".to_string(), true),
+                ("UK".to_string(), true),
+                ("CH".to_string(), true),
+                ("US".to_string(), false),
+            ]),
         }
     }
 }
@@ -174,19 +1055,152 @@ pub(crate) fn plugin(app: &mut App) {
         .init_resource::<MockupAds>()
         .register_type::<MockupFakeLoader>()
         .init_resource::<MockupFakeLoader>()
+        .init_resource::<MockupRng>()
+        .register_type::<MockupAdStrings>()
+        .init_resource::<MockupAdStrings>()
+        .register_type::<MockupConsentForm>()
+        .init_resource::<MockupConsentForm>()
         .register_type::<MockupAdComponent>()
         .register_type::<MockupAdType>()
-        .add_systems(Update, show_ads)
+        .register_type::<MockupChainedReward>()
+        .register_type::<MockupAdCloseButton>()
+        .register_type::<MockupAdSkipButton>()
+        .register_type::<MockupAdSkipAfter>()
+        .register_type::<MockupAdImageFit>()
+        .register_type::<MockupAdCloseableAfter>()
+        .register_type::<MockupBannerClickThrough>()
+        .register_type::<MockupRewardOverride>()
+        .register_type::<MockupOptInPrompt>()
+        .register_type::<MockupOptInAnswer>()
+        .register_type::<MockupAdSkipped>()
+        .register_type::<MockupAdPendingImpression>()
+        .register_type::<MockupMutedIcon>()
+        .register_type::<MockupAdProgressBar>()
+        .register_type::<MockupTimePause>()
+        .register_type::<MockupAdAnimations>()
+        .register_type::<MockupAdAnimation>()
+        .register_type::<InterstitialPacing>()
+        .register_type::<AdDisplay>()
+        .register_type::<AdDisplaySettings>()
+        .register_type::<Reward>()
+        .init_resource::<InterstitialPacing>()
+        .add_systems(
+            Update,
+            (
+                open_fullscreen_ad.before(show_ads).before(emit_impressions),
+                show_ads,
+                remove_skip_buttons.after(show_ads),
+                update_time_left.after(show_ads),
+                update_progress_bars.after(show_ads),
+                emit_impressions,
+                animate_ads,
+                refresh_banners,
+                report_banner_size,
+                fit_ad_images,
+                return_from_banner_click,
+                close_on_escape.run_if(|cfg: Res<MockupAds>| cfg.close_on_escape),
+            ),
+        )
         .add_systems(
             Update,
             MockupFakeLoader::update.run_if(resource_exists::<MockupFakeLoader>),
         )
-        .add_systems(PostStartup, init)
+        .add_systems(Update, MockupConsentForm::update)
         .add_observer(on_despawn)
-        .add_observer(close_clicked);
+        .add_observer(record_rewarded_close)
+        .add_observer(pause_time)
+        .add_observer(resume_time)
+        .add_observer(close_clicked)
+        .add_observer(skip_clicked)
+        .add_observer(retry_load)
+        .add_observer(ad_clicked)
+        .add_observer(opt_in_answered)
+        .add_observer(show_interstitial_every_nth::<InterstitialTrigger>);
+    #[cfg(feature = "audio")]
+    app.add_plugins(audio::plugin);
+    #[cfg(feature = "config_asset")]
+    app.add_plugins(config::plugin);
+    app.add_plugins(event_log::plugin);
+}
+
+/// Gameplay milestone (e.g. level completion) counted by [`InterstitialPacing`].
+#[derive(Event, Debug, Clone, Copy, Default)]
+pub struct InterstitialTrigger;
+
+/// Shows an interstitial ad on every `every`-th trigger.
+/// Setting `every` to 0 disables it.
+#[derive(Debug, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct InterstitialPacing {
+    pub every: u32,
+    pub count: u32,
+}
+
+impl Default for InterstitialPacing {
+    fn default() -> Self {
+        Self { every: 3, count: 0 }
+    }
+}
+
+/// Observer counting `E` triggers towards [`InterstitialPacing`].
+/// [`InterstitialTrigger`] is counted out of the box, other events can be counted with
+/// `app.add_observer(show_interstitial_every_nth::<MyEvent>)`.
+/// When the interstitial is not ready on the N-th trigger it is shown on the next one.
+pub fn show_interstitial_every_nth<E: Event>(_t: On<E>, mut ads: MockupAdsSystem) {
+    if ads.pacing.every == 0 {
+        return;
+    }
+    ads.pacing.count = ads.pacing.count.saturating_add(1);
+    if ads.pacing.count < ads.pacing.every || !ads.is_interstitial_ready() {
+        return;
+    }
+    if ads.show_interstitial() {
+        ads.pacing.count = 0;
+    }
+}
+
+/// Run condition that is true while a mockup ad of this type is ready to be shown,
+/// e.g. `.run_if(ad_ready(AdType::Rewarded))`.
+pub fn ad_ready(
+    ad_type: AdType,
+) -> impl FnMut(Res<MockupAds>, Res<MockupFakeLoader>) -> bool + Clone {
+    move |cfg: Res<MockupAds>, loader: Res<MockupFakeLoader>| {
+        cfg.initialized && loader.is_loaded(ad_type)
+    }
+}
+
+/// Run condition that is true while a mockup interstitial ad is ready to be shown.
+pub fn interstitial_ready(cfg: Res<MockupAds>, loader: Res<MockupFakeLoader>) -> bool {
+    cfg.initialized && loader.is_loaded(AdType::Interstitial)
+}
+
+/// Run condition that is true while a mockup rewarded ad is ready to be shown.
+pub fn rewarded_ready(cfg: Res<MockupAds>, loader: Res<MockupFakeLoader>) -> bool {
+    cfg.initialized && loader.is_loaded(AdType::Rewarded)
 }
 
-fn init(mut ads: MockupAdsSystem) {
+/// Run condition that is true while any mockup ad is displayed.
+/// Banners are counted only when [`MockupAds::banners_count_as_showing`] is set.
+/// Systems can also call it with their own query and config to check it.
+pub fn any_ad_showing(ads: Query<&MockupAdType>, cfg: Res<MockupAds>) -> bool {
+    ads.iter()
+        .any(|ad_type| **ad_type != AdType::Banner || cfg.banners_count_as_showing)
+}
+
+/// Run condition that is true while no mockup ad is displayed,
+/// e.g. `.run_if(no_ad_showing)` on gameplay input systems.
+pub fn no_ad_showing(ads: Query<&MockupAdType>, cfg: Res<MockupAds>) -> bool {
+    !any_ad_showing(ads, cfg)
+}
+
+/// Entity of the displayed mockup ad of this type, e.g. to attach a watermark to it.
+pub fn active_ad_entity(q: &Query<(Entity, &MockupAdType)>, ad_type: AdType) -> Option<Entity> {
+    q.iter()
+        .find(|(_, component_ad_type)| ***component_ad_type == ad_type)
+        .map(|(entity, _)| entity)
+}
+
+pub(crate) fn init(mut ads: MockupAdsSystem) {
     ads.initialize();
 }
 
@@ -197,56 +1211,565 @@ pub struct MockupAdComponent {
     pub auto_close: bool,
 }
 
+impl MockupAdComponent {
+    /// How far the ad has played, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        self.timer.fraction()
+    }
+}
+
+/// Type of a displayed ad and the ad unit id it was loaded with.
 #[derive(Component, Reflect, Deref)]
 #[reflect(Component)]
-pub struct MockupAdType(AdType);
+pub struct MockupAdType {
+    #[deref]
+    ad_type: AdType,
+    ad_id: String,
+}
+
+impl MockupAdType {
+    fn new(ad_type: AdType, ad_id: &str) -> Self {
+        Self {
+            ad_type,
+            ad_id: ad_id.to_string(),
+        }
+    }
+
+    /// Ad unit id passed to the load of this ad.
+    pub fn ad_id(&self) -> &str {
+        &self.ad_id
+    }
+}
 
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct MockupAdTimeLeftText;
 
-#[derive(SystemParam)]
-pub struct MockupAdsSystem<'w, 's> {
-    pub r: ResMut<'w, MockupAds>,
-    pub cmd: Commands<'w, 's>,
-    pub timer: ResMut<'w, MockupFakeLoader>,
+/// Animations of a displayed ad.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct MockupAdAnimations(pub AdAnimations);
+
+/// Enter or exit animation in progress.
+/// The ad is despawned, emitting `AdClosed`, only once its exit animation finishes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupAdAnimation {
+    pub animation: ShowAnimation,
+    pub exit: bool,
+    timer: Timer,
+    base_alpha: Option<f32>,
 }
 
-impl MockupAdsSystem<'_, '_> {
-    pub fn show_fullscreen_ad(&mut self, ad_type: AdType) -> bool {
-        if !self.is_initialized() {
-            return false;
-        }
-        if !self.timer.is_loaded(ad_type) {
-            return false;
+impl MockupAdAnimation {
+    fn new(animation: ShowAnimation, exit: bool, duration_ms: u64) -> Self {
+        Self {
+            animation,
+            exit,
+            timer: Timer::new(Duration::from_millis(duration_ms), TimerMode::Once),
+            base_alpha: None,
         }
-        let settings = match ad_type {
-            AdType::Banner => return false,
-            AdType::Interstitial => &self.r.interstitial,
-            AdType::Rewarded => &self.r.rewarded,
-        };
-        let show_time_left = settings.show_time_left;
-        let auto_close = settings.auto_close;
-        let duration = settings.duration_ms;
-        let mut ss = match &settings.display {
-            AdDisplay::SolidBackground(background_color) => self
-                .cmd
-                .spawn((ad_bundle(duration, ad_type, auto_close), *background_color)),
-            AdDisplay::SolidBackgroundWithText(background_color, text) => self.cmd.spawn((
-                ad_bundle(duration, ad_type, auto_close),
-                *background_color,
-                children![Text::new(text)],
-            )),
-            AdDisplay::Image(handle) => self.cmd.spawn((
-                ad_bundle(duration, ad_type, auto_close),
-                ImageNode::new(handle.clone()),
-            )),
-        };
-        if show_time_left {
-            ss.with_child(time_left());
+    }
+}
+
+/// Pauses `Time<Virtual>` while fullscreen ads are displayed,
+/// present only with [`AdsCommonPlugin::pause_on_fullscreen`](crate::AdsCommonPlugin::pause_on_fullscreen).
+/// Displayed ads themselves run on `Time<Real>`.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct MockupTimePause {
+    /// Number of fullscreen ads currently displayed.
+    active_ads: u32,
+    /// Relative speed restored once the last fullscreen ad closes.
+    relative_speed: f32,
+}
+
+/// Bar displayed on fullscreen ads with [`AdDisplaySettings::show_progress_bar`] set.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupAdProgressBar;
+
+/// Icon displayed on fullscreen ads while [`MockupAds::muted`] is set.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupMutedIcon;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupAdCloseButton;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupAdSkipButton;
+
+/// Time until the player returns from the browser opened by a banner click,
+/// see [`MockupAds::banner_click_through_ms`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupBannerClickThrough(pub Timer);
+
+/// Time after which the skip button of an ad is displayed.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupAdSkipAfter(pub Duration);
+
+/// Time after which the close button of an ad is displayed, removed once it is.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupAdCloseableAfter(pub Duration);
+
+/// Marks an ad that was not laid out on screen yet and so had no impression.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupAdPendingImpression;
+
+/// Marks an ad skipped with its skip button or cancelled with
+/// [`MockupAdsSystem::skip_pending_ad`] before it was displayed.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupAdSkipped;
+
+/// Prompt asking the player to opt in to a rewarded ad granting the reward.
+#[derive(Component, Reflect, Deref)]
+#[reflect(Component)]
+pub struct MockupOptInPrompt(pub Reward);
+
+/// Button answering a [`MockupOptInPrompt`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupOptInAnswer {
+    pub prompt: Entity,
+    pub accept: bool,
+}
+
+/// Reward granted by a rewarded ad instead of the configured one.
+#[derive(Component, Reflect, Deref)]
+#[reflect(Component)]
+pub struct MockupRewardOverride(pub Reward);
+
+/// Marks a rewarded ad shown with [`MockupAdsSystem::show_chained_rewarded`].
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct MockupChainedReward;
+
+#[derive(SystemParam)]
+pub struct MockupAdsSystem<'w, 's> {
+    pub r: ResMut<'w, MockupAds>,
+    pub cmd: Commands<'w, 's>,
+    pub timer: ResMut<'w, MockupFakeLoader>,
+    pub rng: ResMut<'w, MockupRng>,
+    pub images: Option<Res<'w, Assets<Image>>>,
+    pub asset_server: Option<Res<'w, AssetServer>>,
+    pub primary_window: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    pub queue: Res<'w, AdEventQueue>,
+    pub consent_form: ResMut<'w, MockupConsentForm>,
+    pub frequency_cap: Option<ResMut<'w, FrequencyCap>>,
+    pub time: Res<'w, Time>,
+    pub ads_enabled: ResMut<'w, AdsEnabled>,
+    pub strings: Res<'w, MockupAdStrings>,
+    pub spawn_hook: Option<Res<'w, MockupAdSpawnHook>>,
+    pub pacing: ResMut<'w, InterstitialPacing>,
+    pub retries: ResMut<'w, AdRetryState>,
+}
+
+impl MockupAdsSystem<'_, '_> {
+    pub fn show_fullscreen_ad(&mut self, ad_type: AdType) -> bool {
+        self.spawn_fullscreen_ad(ad_type).is_some()
+    }
+
+    /// Show a rewarded ad granting `reward` instead of [`MockupAds::rewarded_ad_reward`]
+    /// for this show only.
+    /// When [`AdDisplaySettings::opt_in_prompt`] is set, the player is asked first.
+    pub fn show_rewarded_with_reward(&mut self, reward: Reward) -> bool {
+        if self.suppressed(AdType::Rewarded) {
+            return false;
+        }
+        if !self.r.rewarded.opt_in_prompt {
+            return self.play_rewarded(reward);
+        }
+        if !self.is_rewarded_ready() {
+            return false;
         }
+        self.spawn_opt_in_prompt(reward);
         true
     }
+
+    fn play_rewarded(&mut self, reward: Reward) -> bool {
+        let Some(entity) = self.spawn_fullscreen_ad(AdType::Rewarded) else {
+            return false;
+        };
+        self.cmd.entity(entity).insert(MockupRewardOverride(reward));
+        true
+    }
+
+    /// Emit `AdSuppressed` and return true while ads are disabled.
+    fn suppressed(&mut self, ad_type: AdType) -> bool {
+        if self.ads_enabled.0 {
+            return false;
+        }
+        self.queue.write(AdMessage::AdSuppressed { ad_type });
+        true
+    }
+
+    /// Emit `SuspiciousActivity` as if the ad network detected fraud,
+    /// for testing fraud handling. Genuine ad completions never emit it.
+    pub fn simulate_fraud_signal(&mut self, reason: impl Into<String>) {
+        self.queue.write(AdMessage::SuspiciousActivity {
+            reason: reason.into(),
+        });
+    }
+
+    /// Z-index of the displayed ads, see [`MockupAds::z_index`].
+    pub fn z_index(&self) -> i32 {
+        self.r.z_index
+    }
+
+    /// Snapshot of the remaining load times.
+    pub fn timers(&self) -> MockupTimers {
+        MockupTimers {
+            rewarded_remaining: self.timer.remaining(AdType::Rewarded),
+            interstitial_remaining: self.timer.remaining(AdType::Interstitial),
+            app_open_remaining: self.timer.remaining(AdType::AppOpen),
+            native_remaining: self.timer.remaining(AdType::Native),
+        }
+    }
+
+    /// Capture the current session state.
+    pub fn snapshot(&self) -> MockupSnapshot {
+        let now = self.time.elapsed();
+        MockupSnapshot {
+            consent: self.r.consent,
+            consent_status: self.r.consent_status,
+            chainable_reward: self.r.chainable_reward.clone(),
+            skip_first_interstitial: self.r.skip_first_interstitial,
+            rewarded_cooldown_left: self
+                .r
+                .rewarded_cooldown_until
+                .map(|until| until.saturating_sub(now))
+                .filter(|left| !left.is_zero()),
+            interstitial_pacing_count: self.pacing.count,
+            frequency_cap: self.frequency_cap.as_ref().map(|cap| cap.session(now)),
+            ads_enabled: self.ads_enabled.0,
+        }
+    }
+
+    /// Restore session state captured with [`MockupAdsSystem::snapshot`].
+    /// The frequency cap session is restored only while a [`FrequencyCap`] is present.
+    /// Initialization is not restored, the mockup initializes as usual.
+    pub fn restore(&mut self, snapshot: MockupSnapshot) {
+        let now = self.time.elapsed();
+        self.r.consent = snapshot.consent;
+        self.r.consent_status = snapshot.consent_status;
+        self.r.chainable_reward = snapshot.chainable_reward;
+        self.r.skip_first_interstitial = snapshot.skip_first_interstitial;
+        self.r.rewarded_cooldown_until = snapshot.rewarded_cooldown_left.map(|left| now + left);
+        self.pacing.count = snapshot.interstitial_pacing_count;
+        if let (Some(cap), Some(session)) = (&mut self.frequency_cap, snapshot.frequency_cap) {
+            cap.restore_session(session, now);
+        }
+        self.ads_enabled.0 = snapshot.ads_enabled;
+    }
+
+    /// Can the last earned reward be multiplied by a chained rewarded ad?
+    pub fn can_chain_reward(&self) -> bool {
+        self.r.chainable_reward.is_some() && self.is_rewarded_ready()
+    }
+
+    /// Show a "double or nothing" rewarded ad following a completed one.
+    /// On completion it emits a `ChainedRewardEarned` with [`MockupAds::chained_reward`],
+    /// replacing the previous reward.
+    pub fn show_chained_rewarded(&mut self) -> bool {
+        let Some(expected_reward) = self.r.chained_reward() else {
+            return false;
+        };
+        let Some(entity) = self.spawn_fullscreen_ad(AdType::Rewarded) else {
+            return false;
+        };
+        self.cmd
+            .entity(entity)
+            .insert((MockupChainedReward, MockupRewardOverride(expected_reward)));
+        true
+    }
+
+    /// Cancel a fullscreen ad about to be shown, emitting `AdClosed` with
+    /// [`AdCloseReason::Skipped`] instead of `AdOpened`.
+    /// A show is cancelled until the ad is opened on the next update, without a queued show
+    /// the next loaded ad of that type is discarded.
+    /// Returns false if nothing was skipped, e.g. while an ad of that type is displayed.
+    pub fn skip_pending_ad(&mut self, ad_type: AdType) -> bool {
+        if matches!(ad_type, AdType::Banner | AdType::Native) {
+            return false;
+        }
+        match self.timer.fullscreen {
+            Some((entity, shown)) if shown == ad_type && self.timer.opening => {
+                self.timer.opening = false;
+                // `on_despawn` emits the `AdClosed` and uses up the load.
+                self.cmd.entity(entity).insert(MockupAdSkipped).despawn();
+                true
+            }
+            // A displayed ad already emitted `AdOpened` and is closed by its own `AdClosed`.
+            Some((_, shown)) if shown == ad_type => false,
+            _ if !self.timer.is_loaded(ad_type) => false,
+            _ => {
+                let ad_id = self.timer.ad_id(ad_type).unwrap_or_default().to_string();
+                self.timer.consume(ad_type);
+                self.queue.write(AdMessage::AdClosed {
+                    ad_type,
+                    ad_id,
+                    reason: AdCloseReason::Skipped,
+                });
+                true
+            }
+        }
+    }
+
+    fn spawn_opt_in_prompt(&mut self, reward: Reward) {
+        let prompt = self
+            .cmd
+            .spawn((
+                Node {
+                    padding: UiRect::all(Val::Px(20.0)),
+                    left: Val::Percent(50.0),
+                    top: Val::Percent(50.0),
+                    margin: UiRect {
+                        left: Val::Px(-150.0),
+                        top: Val::Px(-50.0),
+                        ..Default::default()
+                    },
+                    width: Val::Px(300.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(10.0),
+                    position_type: PositionType::Absolute,
+                    ..Default::default()
+                },
+                BackgroundColor(bevy_color::palettes::tailwind::ZINC_700.into()),
+                bevy_ui::ZIndex(self.r.z_index),
+                children![Text::new(self.strings.format_opt_in_prompt(&reward))],
+                MockupOptInPrompt(reward),
+            ))
+            .id();
+        let row = self
+            .cmd
+            .spawn((
+                Node {
+                    column_gap: Val::Px(10.0),
+                    flex_direction: FlexDirection::Row,
+                    ..Default::default()
+                },
+                ChildOf(prompt),
+            ))
+            .id();
+        for (accept, label, color) in [
+            (
+                true,
+                &self.strings.opt_in_accept,
+                bevy_color::palettes::tailwind::GREEN_500,
+            ),
+            (
+                false,
+                &self.strings.opt_in_decline,
+                bevy_color::palettes::tailwind::RED_400,
+            ),
+        ] {
+            self.cmd.spawn((
+                Button,
+                Node {
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                    ..Default::default()
+                },
+                BackgroundColor(color.into()),
+                MockupOptInAnswer { prompt, accept },
+                ChildOf(row),
+                children![Text::new(label.clone())],
+            ));
+        }
+    }
+
+    fn spawn_fullscreen_ad(&mut self, ad_type: AdType) -> Option<Entity> {
+        if !self.is_initialized() {
+            return None;
+        }
+        if !self.timer.is_loaded(ad_type) {
+            return None;
+        }
+        if self.suppressed(ad_type) {
+            return None;
+        }
+        // Only one fullscreen ad is displayed at a time, so hiding it closes exactly one.
+        if self.timer.fullscreen.is_some() {
+            self.queue.write(AdMessage::AdThrottled { ad_type });
+            return None;
+        }
+        let settings = match ad_type {
+            AdType::Banner | AdType::Native => return None,
+            AdType::Interstitial => &self.r.interstitial,
+            AdType::Rewarded => &self.r.rewarded,
+            AdType::AppOpen => &self.r.app_open,
+        };
+        let now = self.time.elapsed();
+        if let Some(cap) = &self.frequency_cap
+            && !cap.allows(ad_type, now)
+        {
+            self.queue.write(AdMessage::AdThrottled { ad_type });
+            return None;
+        }
+        let show_time_left = settings.show_time_left;
+        let show_progress_bar = settings.show_progress_bar;
+        let auto_close = settings.auto_close
+            || (ad_type == AdType::Rewarded && settings.auto_close_after_reward);
+        let duration = settings.duration_ms;
+        let animations = settings.animations;
+        let skip_after_ms = settings.skip_after_ms;
+        let closeable_after_ms = settings.closeable_after_ms.min(duration);
+        let scrim = settings.scrim;
+        let ad_id = self.timer.ad_id(ad_type).unwrap_or_default().to_string();
+        let mut ss = match &settings.display {
+            AdDisplay::SolidBackground(background_color) => self.cmd.spawn((
+                ad_bundle(duration, ad_type, &ad_id, auto_close),
+                *background_color,
+            )),
+            AdDisplay::SolidBackgroundWithText(background_color, text) => self.cmd.spawn((
+                ad_bundle(duration, ad_type, &ad_id, auto_close),
+                *background_color,
+                children![Text::new(text)],
+            )),
+            AdDisplay::SolidBackgroundWithDefaultText(background_color) => self.cmd.spawn((
+                ad_bundle(duration, ad_type, &ad_id, auto_close),
+                *background_color,
+                children![Text::new(self.strings.ad_body.clone())],
+            )),
+            AdDisplay::Gradient {
+                from,
+                to,
+                direction,
+            } => self.cmd.spawn((
+                ad_bundle(duration, ad_type, &ad_id, auto_close),
+                BackgroundGradient(vec![
+                    LinearGradient::new(direction.angle(), vec![(*from).into(), (*to).into()])
+                        .into(),
+                ]),
+            )),
+            AdDisplay::Image { handle, fit }
+                if image_available(
+                    handle,
+                    self.images.as_deref(),
+                    self.asset_server.as_deref(),
+                ) =>
+            {
+                let mut ss = self
+                    .cmd
+                    .spawn(ad_bundle(duration, ad_type, &ad_id, auto_close));
+                ss.with_child(image_bundle(handle.clone(), *fit));
+                ss
+            }
+            AdDisplay::Image { .. } => self.cmd.spawn((
+                ad_bundle(duration, ad_type, &ad_id, auto_close),
+                BackgroundColor(bevy_color::palettes::tailwind::ZINC_500.into()),
+                children![Text::new(self.r.image_fallback_text.clone())],
+            )),
+        };
+        ss.insert(bevy_ui::ZIndex(self.r.z_index));
+        if let Some(scrim) = scrim {
+            ss.with_child(scrim_bundle(scrim, self.r.z_index));
+        }
+        if show_time_left {
+            ss.with_child(time_left(&self.r.time_left));
+        }
+        if show_progress_bar {
+            ss.with_child(progress_bar());
+        }
+        if self.r.muted {
+            ss.with_child(muted_icon(&self.strings.muted));
+        }
+        if let Some(skip_after_ms) = skip_after_ms {
+            ss.insert(MockupAdSkipAfter(Duration::from_millis(skip_after_ms)));
+        }
+        if !auto_close {
+            ss.insert(MockupAdCloseableAfter(Duration::from_millis(
+                closeable_after_ms,
+            )));
+        }
+        insert_animations(&mut ss, animations);
+        if let Some(hook) = &self.spawn_hook {
+            // A panicking hook is logged instead of taking the show down with it.
+            let hooked = std::panic::catch_unwind(AssertUnwindSafe(|| (hook.0)(&mut ss, ad_type)));
+            if hooked.is_err() {
+                bevy_log::error!("MockupAdSpawnHook panicked for a {ad_type} ad");
+            }
+        }
+        let entity = ss.id();
+        self.timer.fullscreen = Some((entity, ad_type));
+        self.timer.opening = true;
+        // Recorded once the ad is spawned, refused shows don't count towards the cap.
+        if let Some(cap) = &mut self.frequency_cap {
+            cap.record_shown(ad_type, now);
+        }
+        Some(entity)
+    }
+
+    /// Load time of the ad type with [`MockupAds::load_jitter_ms`] applied.
+    fn load_duration(&mut self, ad_type: AdType) -> Duration {
+        let duration = self.timer.duration_for(ad_type);
+        let jitter = self.r.load_jitter_ms;
+        if jitter == 0 {
+            return duration;
+        }
+        let ms = duration.as_millis() as u64;
+        let ms = self
+            .rng
+            .random_range(ms.saturating_sub(jitter)..=ms.saturating_add(jitter));
+        Duration::from_millis(ms)
+    }
+
+    /// Fail the load right away while [`MockupAds::network_available`] is false.
+    fn offline(&mut self, ad_type: AdType, ad_id: &str) -> bool {
+        if self.r.network_available {
+            return false;
+        }
+        self.queue.write(AdMessage::AdFailedToLoad {
+            ad_type,
+            ad_id: ad_id.to_string(),
+            error: AdError::NetworkError,
+        });
+        true
+    }
+
+    fn start_load(&mut self, ad_type: AdType, ad_id: &str) -> bool {
+        self.retries.reset(ad_type);
+        self.timer.max_preloaded = self.r.max_preloaded;
+        self.timer.personalized = self.r.consent.personalized_ads;
+        if !self.ads_enabled.0 || !self.timer.can_start_load(ad_type) {
+            return false;
+        }
+        if self.offline(ad_type, ad_id) {
+            return false;
+        }
+        bevy_log::debug!(
+            "Requesting {ad_type} ad (personalized: {})",
+            self.r.consent.personalized_ads
+        );
+        if self.r.fail_next_load == Some(ad_type) {
+            self.r.fail_next_load = None;
+            let duration = self.load_duration(ad_type);
+            let error = Some(self.r.load_error.clone());
+            return self
+                .timer
+                .start_load_with(ad_type, ad_id, duration, None, error);
+        }
+        if self.r.waterfall.is_empty() {
+            let duration = self.load_duration(ad_type);
+            let network = Some(self.r.fake_network.clone());
+            return self
+                .timer
+                .start_load_with(ad_type, ad_id, duration, network, None);
+        }
+        let (latency, network) = run_waterfall(&self.r.waterfall, &mut self.rng);
+        let error = network.is_none().then_some(AdError::NoFill);
+        self.timer
+            .start_load_with(ad_type, ad_id, latency, network, error)
+    }
 }
 
 impl AdManager for MockupAdsSystem<'_, '_> {
@@ -260,23 +1783,87 @@ impl AdManager for MockupAdsSystem<'_, '_> {
         }
         self.timer
             .set_duration(Duration::from_millis(self.r.loading_time_ms));
+        for (ad_type, load_ms) in [
+            (AdType::Interstitial, self.r.interstitial_load_ms),
+            (AdType::Rewarded, self.r.rewarded_load_ms),
+        ] {
+            if let Some(load_ms) = load_ms {
+                self.timer
+                    .set_duration_for(ad_type, Duration::from_millis(load_ms));
+            }
+        }
+        *self.rng = MockupRng::new(self.r.rng_seed);
 
         self.r.initialized = true;
-        crate::write_event_to_queue(AdMessage::Initialized { success: true });
+        self.queue.write(AdMessage::Initialized { success: true });
         true
     }
 
+    /// At most one banner is displayed, showing it again while it is displayed does nothing.
     fn show_banner(&mut self) -> bool {
-        self.cmd.spawn(banner_bundle());
+        if self.suppressed(AdType::Banner) {
+            return false;
+        }
+        if self.timer.banner.is_some() {
+            return true;
+        }
+        let width = self.get_banner_width("");
+        let height = self.get_banner_height("");
+        let mut banner = self.cmd.spawn(banner_bundle(
+            width as f32,
+            height as f32,
+            self.r.banner_position,
+            &self.timer.banner_ad_id,
+        ));
+        banner.insert(bevy_ui::ZIndex(self.r.z_index));
+        if !self.r.banner_clickable {
+            banner.insert(Pickable::IGNORE);
+        }
+        insert_animations(&mut banner, self.r.banner_animations);
+        self.timer.banner = Some(banner.id());
+        self.queue.write(AdMessage::AdOpened {
+            ad_type: AdType::Banner,
+            ad_id: self.timer.banner_ad_id.clone(),
+        });
         true
     }
 
+    fn set_banner_refresh_interval(&mut self, interval: Option<Duration>) {
+        self.r.banner_refresh_interval = interval;
+    }
+
+    fn set_banner_position(&mut self, position: BannerPosition) {
+        self.r.banner_position = position;
+        self.cmd.run_system_cached_with(position_banners, position);
+    }
+
     fn show_interstitial(&mut self) -> bool {
+        if self.suppressed(AdType::Interstitial) {
+            return false;
+        }
+        if self.r.skip_first_interstitial && self.is_interstitial_ready() {
+            self.r.skip_first_interstitial = false;
+            self.queue.write(AdMessage::AdSuppressed {
+                ad_type: AdType::Interstitial,
+            });
+            return false;
+        }
+        if self
+            .r
+            .rewarded_cooldown_until
+            .is_some_and(|until| self.time.elapsed() < until)
+        {
+            self.queue.write(AdMessage::AdThrottled {
+                ad_type: AdType::Interstitial,
+            });
+            return false;
+        }
         self.show_fullscreen_ad(AdType::Interstitial)
     }
 
     fn show_rewarded(&mut self) -> bool {
-        self.show_fullscreen_ad(AdType::Rewarded)
+        let reward = self.r.rewarded_ad_reward.clone();
+        self.show_rewarded_with_reward(reward)
     }
 
     fn hide_banner(&mut self) -> bool {
@@ -295,20 +1882,199 @@ impl AdManager for MockupAdsSystem<'_, '_> {
         true
     }
 
-    fn load_banner(&mut self, _ad_id: &str) -> bool {
+    fn show_app_open(&mut self) -> bool {
+        self.show_fullscreen_ad(AdType::AppOpen)
+    }
+
+    /// At most one native ad is displayed, showing another one while it is displayed fails.
+    fn show_native(&mut self) -> bool {
+        if !self.is_native_ready()
+            || self.timer.native_ad.is_some()
+            || self.suppressed(AdType::Native)
+        {
+            return false;
+        }
+        let ad_id = self
+            .timer
+            .ad_id(AdType::Native)
+            .unwrap_or_default()
+            .to_string();
+        let native = self.cmd.spawn((
+            native_bundle(&self.r.native, &ad_id),
+            bevy_ui::ZIndex(self.r.z_index),
+        ));
+        self.timer.native_ad = Some(native.id());
+        self.queue.write(AdMessage::AdOpened {
+            ad_type: AdType::Native,
+            ad_id,
+        });
+        true
+    }
+
+    fn hide_native(&mut self) -> bool {
+        self.cmd.run_system_cached_with(hide_ad, AdType::Native);
+        true
+    }
+
+    fn hide_app_open(&mut self) -> bool {
+        self.cmd.run_system_cached_with(hide_ad, AdType::AppOpen);
+        true
+    }
+
+    fn loaded_ad_network(&self, ad_type: AdType) -> Option<String> {
+        self.timer.loaded_network(ad_type)
+    }
+
+    fn loaded_count(&self, ad_type: AdType) -> usize {
+        if !self.is_initialized() {
+            return 0;
+        }
+        self.timer.loaded_count(ad_type)
+    }
+
+    fn is_loading(&self, ad_type: AdType) -> bool {
+        self.timer.is_loading(ad_type)
+    }
+
+    fn set_ad_volume(&mut self, volume: f32) {
+        self.r.volume = volume.clamp(0.0, 1.0);
+    }
+
+    fn set_muted(&mut self, muted: bool) {
+        self.r.muted = muted;
+        self.cmd.run_system_cached_with(sync_muted_icons, muted);
+    }
+
+    fn destroy_ad(&mut self, ad_type: AdType) -> bool {
+        self.cmd.run_system_cached_with(hide_ad, ad_type);
+        self.timer.reset(ad_type);
         true
     }
 
-    fn load_interstitial(&mut self, _ad_id: &str) -> bool {
-        self.timer.start_load(AdType::Interstitial);
+    /// Despawns all ads and opt-in prompts right away, skipping exit animations,
+    /// and discards all loaded ads.
+    fn dismiss_all(&mut self) {
+        self.cmd.run_system_cached(dismiss_all_ads);
+        for ad_type in [
+            AdType::Banner,
+            AdType::Interstitial,
+            AdType::Rewarded,
+            AdType::AppOpen,
+            AdType::Native,
+        ] {
+            self.timer.reset(ad_type);
+        }
+    }
+
+    fn load_banner(&mut self, ad_id: &str) -> bool {
+        self.retries.reset(AdType::Banner);
+        if !self.ads_enabled.0 || self.offline(AdType::Banner, ad_id) {
+            return false;
+        }
+        self.timer.banner_ad_id = ad_id.to_string();
         true
     }
 
-    fn load_rewarded(&mut self, _ad_id: &str) -> bool {
-        self.timer.start_load(AdType::Rewarded);
+    fn set_ads_enabled(&mut self, enabled: bool) {
+        self.ads_enabled.0 = enabled;
+        if enabled {
+            return;
+        }
+        for ad_type in [
+            AdType::Banner,
+            AdType::Interstitial,
+            AdType::Rewarded,
+            AdType::AppOpen,
+            AdType::Native,
+        ] {
+            self.cmd.run_system_cached_with(hide_ad, ad_type);
+        }
+    }
+
+    fn load_interstitial(&mut self, ad_id: &str) -> bool {
+        self.start_load(AdType::Interstitial, ad_id)
+    }
+
+    fn load_rewarded(&mut self, ad_id: &str) -> bool {
+        self.start_load(AdType::Rewarded, ad_id)
+    }
+
+    fn load_app_open(&mut self, ad_id: &str) -> bool {
+        self.start_load(AdType::AppOpen, ad_id)
+    }
+
+    fn load_native(&mut self, ad_id: &str) -> bool {
+        self.start_load(AdType::Native, ad_id)
+    }
+
+    fn request_consent(&mut self) -> bool {
+        if self.consent_form.is_pending() {
+            return false;
+        }
+        if !self.r.network_available {
+            self.queue.write(AdMessage::ConsentGathered {
+                success: false,
+                error: Some(AdError::NetworkError),
+                state: self.r.consent,
+            });
+            return false;
+        }
+        if !self.consent_required_in_region() {
+            self.r.consent_status = ConsentStatus::NotRequired;
+            self.queue.write(AdMessage::ConsentGathered {
+                success: true,
+                error: None,
+                state: self.r.consent,
+            });
+            return true;
+        }
+        self.r.consent_status = ConsentStatus::Required;
+        self.consent_form.0 = Some(Timer::new(
+            Duration::from_millis(self.r.consent_form_ms),
+            TimerMode::Once,
+        ));
         true
     }
 
+    fn consent_status(&self) -> ConsentStatus {
+        self.r.consent_status
+    }
+
+    fn set_consent(&mut self, state: ConsentState) {
+        self.r.consent = state;
+        self.r.consent_status = ConsentStatus::Obtained;
+        self.queue.write(AdMessage::ConsentGathered {
+            success: true,
+            error: None,
+            state,
+        });
+    }
+
+    fn consent_state(&self) -> ConsentState {
+        self.r.consent
+    }
+
+    fn get_banner_width(&self, _ad_id: &str) -> i32 {
+        let screen_width = self
+            .primary_window
+            .single()
+            .map(|window| window.width() as i32)
+            .unwrap_or(320);
+        self.r.banner_size.width(screen_width)
+    }
+
+    fn get_banner_height(&self, _ad_id: &str) -> i32 {
+        self.r.banner_size.height()
+    }
+
+    fn consent_required_in_region(&self) -> bool {
+        self.r
+            .consent_regions
+            .get(&self.r.simulated_region)
+            .copied()
+            .unwrap_or(false)
+    }
+
     fn is_interstitial_ready(&self) -> bool {
         if !self.is_initialized() {
             return false;
@@ -322,66 +2088,419 @@ impl AdManager for MockupAdsSystem<'_, '_> {
         }
         self.timer.is_loaded(AdType::Rewarded)
     }
+
+    fn is_app_open_ready(&self) -> bool {
+        if !self.is_initialized() {
+            return false;
+        }
+        self.timer.is_loaded(AdType::AppOpen)
+    }
+
+    fn is_native_ready(&self) -> bool {
+        if !self.is_initialized() {
+            return false;
+        }
+        self.timer.is_loaded(AdType::Native)
+    }
+}
+
+#[derive(QueryData)]
+#[query_data(mutable)]
+struct ShownAd {
+    entity: Entity,
+    component: &'static mut MockupAdComponent,
+    ad_type: &'static MockupAdType,
+    chained: Has<MockupChainedReward>,
+    reward_override: Option<&'static MockupRewardOverride>,
+    animations: Option<&'static MockupAdAnimations>,
+    skip_after: Option<&'static MockupAdSkipAfter>,
+    closeable_after: Option<&'static MockupAdCloseableAfter>,
+    animation: Option<&'static MockupAdAnimation>,
+}
+
+fn show_ads(
+    mut q: Query<ShownAd>,
+    time: Res<Time<Real>>,
+    mut commands: Commands,
+    mut cfg: ResMut<MockupAds>,
+    strings: Res<MockupAdStrings>,
+    queue: Res<AdEventQueue>,
+    #[cfg(feature = "diagnostics")] mut diagnostics: bevy_diagnostic::Diagnostics,
+) {
+    #[cfg(feature = "diagnostics")]
+    let start = bevy_platform::time::Instant::now();
+    for ShownAdItem {
+        entity,
+        mut component,
+        ad_type,
+        chained,
+        reward_override,
+        animations,
+        skip_after,
+        closeable_after,
+        animation,
+    } in q.iter_mut()
+    {
+        // A closed ad is frozen while it leaves, it earns no reward and gets no buttons.
+        if animation.is_some_and(|animation| animation.exit) {
+            continue;
+        }
+        component.timer.tick(time.delta());
+        if let Some(skip_after) = skip_after
+            && component.timer.elapsed() >= skip_after.0
+            && !component.timer.is_finished()
+        {
+            commands.entity(entity).remove::<MockupAdSkipAfter>();
+            commands.spawn((skip_btn(&strings.skip), ChildOf(entity)));
+        }
+        if let Some(closeable_after) = closeable_after
+            && component.timer.elapsed() >= closeable_after.0
+        {
+            commands.entity(entity).remove::<MockupAdCloseableAfter>();
+            commands.spawn((close_btn(&cfg.close_button), ChildOf(entity)));
+        }
+        if component.timer.just_finished() {
+            if ad_type.eq(&AdType::Rewarded) {
+                let reward = reward_override
+                    .map(|reward| reward.0.clone())
+                    .unwrap_or_else(|| cfg.rewarded_ad_reward.clone());
+                if chained {
+                    // The combined reward replaces the previous one and can't be chained again.
+                    let replaces = cfg.chainable_reward.take().map_or(0, |p| p.amount);
+                    queue.write(AdMessage::ChainedRewardEarned {
+                        amount: reward.amount,
+                        reward_type: reward.type_name,
+                        replaces,
+                    });
+                } else {
+                    cfg.chainable_reward = Some(reward.clone());
+                    for reward in std::iter::once(reward).chain(cfg.extra_rewards.iter().cloned()) {
+                        queue.write(AdMessage::RewardedAdEarnedReward {
+                            amount: reward.amount,
+                            reward_type: reward.type_name,
+                        });
+                    }
+                }
+            }
+            // The reward is queued before the despawn command is applied, so
+            // `AdClosed` from `on_despawn` always follows it.
+            if component.auto_close {
+                dismiss_ad(&mut commands, entity, animations);
+            }
+        }
+    }
+    #[cfg(feature = "diagnostics")]
+    diagnostics.add_measurement(&crate::diagnostics::MOCKUP_SHOW_ADS, || {
+        start.elapsed().as_secs_f64() * 1000.0
+    });
+}
+
+/// Update the progress bar of every ad from its own timer.
+fn update_progress_bars(
+    ads: Query<&MockupAdComponent>,
+    mut bars: Query<(&mut Node, &ChildOf), With<MockupAdProgressBar>>,
+) {
+    for (mut node, parent) in bars.iter_mut() {
+        let Ok(component) = ads.get(parent.0) else {
+            continue;
+        };
+        node.width = Val::Percent(component.progress() * 100.0);
+    }
+}
+
+/// Update the countdown of every ad from its own timer.
+fn update_time_left(
+    ads: Query<&MockupAdComponent>,
+    mut texts: Query<(&mut Text, &ChildOf), With<MockupAdTimeLeftText>>,
+    strings: Res<MockupAdStrings>,
+    cfg: Res<MockupAds>,
+) {
+    for (mut text, parent) in texts.iter_mut() {
+        let Ok(component) = ads.get(parent.0) else {
+            continue;
+        };
+        if component.timer.is_finished() {
+            continue;
+        }
+        text.0 = strings.format_time_left(component.timer.remaining_secs(), cfg.time_left.format);
+    }
+}
+
+/// Write `AdOpened` for the fullscreen ad shown since the last update, followed by
+/// `RewardedAdStarted` for rewarded ads. Runs before impressions, so it always precedes
+/// the `AdImpression` of the ad.
+fn open_fullscreen_ad(
+    mut loader: ResMut<MockupFakeLoader>,
+    q: Query<(&MockupAdType, Option<&MockupRewardOverride>)>,
+    queue: Res<AdEventQueue>,
+) {
+    if !loader.opening {
+        return;
+    }
+    loader.opening = false;
+    let Some((entity, _)) = loader.fullscreen else {
+        return;
+    };
+    let Ok((ad_type, reward)) = q.get(entity) else {
+        return;
+    };
+    queue.write(AdMessage::AdOpened {
+        ad_type: **ad_type,
+        ad_id: ad_type.ad_id.clone(),
+    });
+    if let Some(reward) = reward {
+        queue.write(AdMessage::RewardedAdStarted {
+            expected_reward: reward.0.clone(),
+        });
+    }
+}
+
+fn emit_impressions(
+    mut commands: Commands,
+    q: Query<(Entity, &MockupAdType, &ComputedNode), With<MockupAdPendingImpression>>,
+    queue: Res<AdEventQueue>,
+) {
+    for (entity, ad_type, node) in q.iter() {
+        // Layout runs after spawning, so a non-zero size means the ad was already rendered.
+        if node.is_empty() {
+            continue;
+        }
+        queue.write(AdMessage::AdImpression { ad_type: **ad_type });
+        commands
+            .entity(entity)
+            .remove::<MockupAdPendingImpression>();
+    }
+}
+
+fn hide_ad(
+    In(ad_type): In<AdType>,
+    mut commands: Commands,
+    q: Query<(Entity, &MockupAdType, Option<&MockupAdAnimations>)>,
+) {
+    for (entity, component_ad_type, animations) in q.iter() {
+        if !component_ad_type.eq(&ad_type) {
+            continue;
+        }
+        dismiss_ad(&mut commands, entity, animations);
+    }
+}
+
+fn dismiss_all_ads(
+    mut commands: Commands,
+    ads: Query<Entity, With<MockupAdType>>,
+    prompts: Query<Entity, With<MockupOptInPrompt>>,
+) {
+    for entity in ads.iter().chain(prompts.iter()) {
+        commands.entity(entity).try_despawn();
+    }
+}
+
+/// Despawn an ad, after playing its exit animation if it has one.
+fn dismiss_ad(commands: &mut Commands, entity: Entity, animations: Option<&MockupAdAnimations>) {
+    let Ok(mut e) = commands.get_entity(entity) else {
+        return;
+    };
+    match animations.filter(|animations| animations.0.exit != ShowAnimation::None) {
+        Some(animations) => {
+            e.try_insert(MockupAdAnimation::new(
+                animations.0.exit,
+                true,
+                animations.0.duration_ms,
+            ));
+        }
+        None => {
+            e.try_despawn();
+        }
+    }
 }
 
-fn show_ads(
-    mut q: Query<(Entity, &mut MockupAdComponent, &MockupAdType)>,
-    mut qq: Query<&mut Text, With<MockupAdTimeLeftText>>,
-    time: Res<Time>,
+fn insert_animations(entity: &mut EntityCommands, animations: AdAnimations) {
+    entity.insert(MockupAdAnimations(animations));
+    if animations.enter != ShowAnimation::None {
+        entity.insert(MockupAdAnimation::new(
+            animations.enter,
+            false,
+            animations.duration_ms,
+        ));
+    }
+}
+
+fn animate_ads(
     mut commands: Commands,
-    cfg: Res<MockupAds>,
+    time: Res<Time<Real>>,
+    mut q: Query<(
+        Entity,
+        &mut MockupAdAnimation,
+        &mut UiTransform,
+        &mut BackgroundColor,
+    )>,
 ) {
-    for (entity, mut component, ad_type) in q.iter_mut() {
-        component.timer.tick(time.delta());
-        if component.timer.just_finished() {
-            if ad_type.eq(&AdType::Rewarded) {
-                crate::write_event_to_queue(AdMessage::RewardedAdEarnedReward {
-                    amount: cfg.rewarded_ad_reward.amount,
-                    reward_type: cfg.rewarded_ad_reward.type_name.clone(),
-                });
-            }
-            if component.auto_close {
-                commands.entity(entity).try_despawn();
-            } else {
-                commands.spawn((close_btn(), ChildOf(entity)));
-            }
+    for (entity, mut animation, mut transform, mut background) in q.iter_mut() {
+        let base_alpha = *animation.base_alpha.get_or_insert(background.0.alpha());
+        animation.timer.tick(time.delta());
+        let progress = animation.timer.fraction();
+        // How much of the ad is shown, from 0.0 when hidden to 1.0 when fully shown.
+        let shown = if animation.exit {
+            1.0 - progress
         } else {
-            for mut text in qq.iter_mut() {
-                text.0 = format!("{:.2}s left", component.timer.remaining_secs());
+            progress
+        };
+        *transform = UiTransform::IDENTITY;
+        match animation.animation {
+            ShowAnimation::None => {}
+            ShowAnimation::Fade => background.0.set_alpha(base_alpha * shown),
+            ShowAnimation::SlideUp | ShowAnimation::SlideDown => {
+                // Entering starts one node height away, leaving ends one node height away.
+                let direction = match animation.animation {
+                    ShowAnimation::SlideUp => -1.0,
+                    _ => 1.0,
+                };
+                let offset = if animation.exit {
+                    direction * progress
+                } else {
+                    -direction * (1.0 - progress)
+                };
+                transform.translation = Val2::percent(0.0, offset * 100.0);
             }
+            ShowAnimation::Scale => transform.scale = Vec2::splat(shown),
+        }
+        if !animation.timer.is_finished() {
+            continue;
+        }
+        if animation.exit {
+            commands.entity(entity).try_despawn();
+        } else {
+            background.0.set_alpha(base_alpha);
+            *transform = UiTransform::IDENTITY;
+            commands.entity(entity).try_remove::<MockupAdAnimation>();
         }
     }
 }
 
-fn hide_ad(In(ad_type): In<AdType>, mut commands: Commands, q: Query<(Entity, &MockupAdType)>) {
-    for (entity, component_ad_type) in q.iter() {
-        if !component_ad_type.eq(&ad_type) {
-            continue;
-        }
-        let Ok(mut e) = commands.get_entity(entity) else {
-            continue;
-        };
-        e.try_despawn();
+fn record_rewarded_close(
+    t: On<Remove, MockupAdType>,
+    q: Query<&MockupAdType>,
+    cfg: Option<ResMut<MockupAds>>,
+    time: Res<Time>,
+) {
+    if let (Ok(ad), Some(mut cfg)) = (q.get(t.entity), cfg)
+        && **ad == AdType::Rewarded
+    {
+        cfg.rewarded_cooldown_until = Some(time.elapsed() + cfg.post_rewarded_cooldown);
     }
 }
 
 fn on_despawn(
     t: On<Remove, MockupAdType>,
-    q: Query<&MockupAdType>,
+    q: Query<(
+        &MockupAdType,
+        Has<MockupAdSkipped>,
+        Has<MockupAdPendingImpression>,
+    )>,
     timer: Option<ResMut<MockupFakeLoader>>,
+    cfg: Option<Res<MockupAds>>,
+    rng: Option<ResMut<MockupRng>>,
+    queue: Option<Res<AdEventQueue>>,
 ) {
-    let Ok(ad_type_component) = q.get(t.entity) else {
+    let Ok((ad_type_component, skipped, pending_impression)) = q.get(t.entity) else {
         bevy_log::warn!("Failed to get component info");
         return;
     };
     let ad_type = **ad_type_component;
-    crate::write_event_to_queue(AdMessage::AdClosed { ad_type });
+    let reason = if skipped {
+        AdCloseReason::Skipped
+    } else {
+        AdCloseReason::Closed
+    };
+    let fullscreen = !matches!(ad_type, AdType::Banner | AdType::Native);
     if let Some(mut timer) = timer {
-        timer.reset(ad_type_component.0);
+        match ad_type {
+            AdType::Banner => {
+                if timer.banner == Some(t.entity) {
+                    timer.banner = None;
+                }
+            }
+            _ => timer.consume(ad_type),
+        }
+        if timer.native_ad == Some(t.entity) {
+            timer.native_ad = None;
+        }
+        if timer
+            .fullscreen
+            .is_some_and(|(entity, _)| entity == t.entity)
+        {
+            timer.fullscreen = None;
+            timer.opening = false;
+        }
+    }
+    let Some(queue) = queue else {
+        return;
+    };
+    // Revenue is paid for every rendered ad, including ones skipped by the user.
+    if fullscreen
+        && !pending_impression
+        && let (Some(cfg), Some(mut rng)) = (cfg, rng)
+    {
+        // eCPM is the revenue per thousand impressions, drawn within +-50% of the mean.
+        let value_micros =
+            (cfg.fake_ecpm_micros as f64 / 1000.0 * rng.random_range(0.5..1.5)) as i64;
+        queue.write(AdMessage::AdRevenuePaid {
+            ad_type,
+            value_micros,
+            currency: "USD".to_string(),
+            precision: "ESTIMATED".to_string(),
+        });
+    }
+    queue.write(AdMessage::AdClosed {
+        ad_type,
+        ad_id: ad_type_component.ad_id.clone(),
+        reason,
+    });
+}
+
+fn pause_time(
+    t: On<Add, MockupAdType>,
+    q: Query<&MockupAdType>,
+    pause: Option<ResMut<MockupTimePause>>,
+    time: Option<ResMut<Time<Virtual>>>,
+) {
+    let (Some(mut pause), Some(mut time)) = (pause, time) else {
+        return;
+    };
+    if q.get(t.entity)
+        .is_ok_and(|ad_type| matches!(**ad_type, AdType::Banner | AdType::Native))
+    {
+        return;
+    }
+    if pause.active_ads == 0 {
+        pause.relative_speed = time.relative_speed();
+        time.set_relative_speed(0.0);
+    }
+    pause.active_ads += 1;
+}
+
+fn resume_time(
+    t: On<Remove, MockupAdType>,
+    q: Query<&MockupAdType>,
+    pause: Option<ResMut<MockupTimePause>>,
+    time: Option<ResMut<Time<Virtual>>>,
+) {
+    let (Some(mut pause), Some(mut time)) = (pause, time) else {
+        return;
+    };
+    if pause.active_ads == 0
+        || q.get(t.entity)
+            .is_ok_and(|ad_type| matches!(**ad_type, AdType::Banner | AdType::Native))
+    {
+        return;
+    }
+    pause.active_ads -= 1;
+    if pause.active_ads == 0 {
+        time.set_relative_speed(pause.relative_speed);
     }
 }
 
-fn ad_bundle(duration_ms: u64, ad_type: AdType, auto_close: bool) -> impl Bundle {
+fn ad_bundle(duration_ms: u64, ad_type: AdType, ad_id: &str, auto_close: bool) -> impl Bundle {
     (
         Node {
             width: Val::Percent(100.0),
@@ -398,44 +2517,216 @@ fn ad_bundle(duration_ms: u64, ad_type: AdType, auto_close: bool) -> impl Bundle
             timer: bevy_time::Timer::new(Duration::from_millis(duration_ms), TimerMode::Once),
             auto_close,
         },
-        MockupAdType(ad_type),
-        bevy_ui::ZIndex(500),
+        MockupAdType::new(ad_type, ad_id),
+        MockupAdPendingImpression,
+    )
+}
+
+fn time_left(style: &TimeLeftStyle) -> impl Bundle + use<> {
+    const MARGIN: Val = Val::Px(5.0);
+    let mut node = Node {
+        position_type: PositionType::Absolute,
+        ..Default::default()
+    };
+    match style.anchor {
+        BannerPosition::Top | BannerPosition::TopLeft | BannerPosition::TopRight => {
+            node.top = MARGIN;
+        }
+        BannerPosition::Bottom | BannerPosition::BottomLeft | BannerPosition::BottomRight => {
+            node.bottom = MARGIN;
+        }
+        BannerPosition::Custom { top, left } => {
+            node.top = Val::Px(top);
+            node.left = Val::Px(left);
+        }
+    }
+    // `Top` and `Bottom` are centered by the ad's `align_items`.
+    match style.anchor {
+        BannerPosition::TopLeft | BannerPosition::BottomLeft => node.left = MARGIN,
+        BannerPosition::TopRight | BannerPosition::BottomRight => node.right = MARGIN,
+        _ => {}
+    }
+    (
+        node,
+        Text::new(""),
+        TextFont::from_font_size(style.font_size),
+        TextColor(style.color),
+        MockupAdTimeLeftText,
+        bevy_ui::ZIndex(1),
+        bevy_ui::widget::TextShadow::default(),
+    )
+}
+
+/// Image of an [`AdDisplay::Image`] ad, sized by `fit_ad_images` to keep its aspect ratio.
+#[derive(Component, Reflect, Deref)]
+#[reflect(Component)]
+pub struct MockupAdImageFit(pub ImageFit);
+
+/// Image filling the ad, clipped to the screen.
+fn image_bundle(handle: Handle<Image>, fit: ImageFit) -> impl Bundle {
+    let image = match fit {
+        ImageFit::Stretch => ImageNode::new(handle).with_mode(NodeImageMode::Stretch),
+        ImageFit::Contain | ImageFit::Cover => ImageNode::new(handle),
+    };
+    (
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            overflow: Overflow::clip(),
+            ..Default::default()
+        },
+        BackgroundColor(Color::BLACK),
+        children![(
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_shrink: 0.0,
+                ..Default::default()
+            },
+            image,
+            MockupAdImageFit(fit),
+        )],
+    )
+}
+
+/// Size `Contain` and `Cover` ad images from their aspect ratio and the screen size.
+fn fit_ad_images(
+    mut q: Query<(&mut Node, &ImageNode, &MockupAdImageFit, &ChildOf)>,
+    parents: Query<&ComputedNode>,
+    images: Option<Res<Assets<Image>>>,
+) {
+    let Some(images) = images else {
+        return;
+    };
+    for (mut node, image_node, fit, parent) in q.iter_mut() {
+        let (Some(image), Ok(parent)) = (images.get(&image_node.image), parents.get(parent.0))
+        else {
+            continue;
+        };
+        let screen = parent.size() * parent.inverse_scale_factor;
+        let image_size = image.size_f32();
+        if image_size.min_element() <= 0.0 || screen.min_element() <= 0.0 {
+            continue;
+        }
+        let scale = screen / image_size;
+        let scale = match **fit {
+            ImageFit::Stretch => continue,
+            ImageFit::Contain => scale.min_element(),
+            ImageFit::Cover => scale.max_element(),
+        };
+        let size = image_size * scale;
+        let (width, height) = (Val::Px(size.x), Val::Px(size.y));
+        if node.width != width || node.height != height {
+            node.width = width;
+            node.height = height;
+        }
+    }
+}
+
+/// Overlay behind an ad, despawned together with it.
+fn scrim_bundle(color: BackgroundColor, ad_z_index: i32) -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            ..Default::default()
+        },
+        color,
+        // Children are drawn above their parent, a global z-index puts it below the ad.
+        bevy_ui::GlobalZIndex(ad_z_index - 1),
+        Pickable::IGNORE,
     )
 }
 
-fn time_left() -> impl Bundle {
+fn progress_bar() -> impl Bundle {
     (
         Node {
             position_type: PositionType::Absolute,
-            top: Val::Px(5.0),
+            bottom: Val::Px(0.0),
+            left: Val::Px(0.0),
+            width: Val::Percent(0.0),
+            height: Val::Px(4.0),
+            ..Default::default()
+        },
+        BackgroundColor(bevy_color::palettes::tailwind::AMBER_400.into()),
+        MockupAdProgressBar,
+        bevy_ui::ZIndex(1),
+    )
+}
+
+fn muted_icon(label: &str) -> impl Bundle + use<> {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(5.0),
             left: Val::Px(5.0),
             ..Default::default()
         },
-        Text::new(""),
-        MockupAdTimeLeftText,
+        Text::new(label),
+        MockupMutedIcon,
+        bevy_ui::ZIndex(1),
         bevy_ui::widget::TextShadow::default(),
     )
 }
 
-fn close_btn() -> impl Bundle {
+fn sync_muted_icons(
+    In(muted): In<bool>,
+    mut commands: Commands,
+    ads: Query<Entity, With<MockupAdComponent>>,
+    icons: Query<Entity, With<MockupMutedIcon>>,
+    strings: Res<MockupAdStrings>,
+) {
+    for icon in icons.iter() {
+        commands.entity(icon).try_despawn();
+    }
+    if !muted {
+        return;
+    }
+    for ad in ads.iter() {
+        commands.spawn((muted_icon(&strings.muted), ChildOf(ad)));
+    }
+}
+
+fn close_btn(style: &CloseButtonStyle) -> impl Bundle + use<> {
+    let mut accessibility = AccessKitNode::new(Role::Button);
+    accessibility.set_label(style.accessibility_label.as_str());
     (
         Button,
+        MockupAdCloseButton,
+        bevy_ui::ZIndex(1),
         Node {
-            width: Val::Px(30.0),
-            height: Val::Px(30.0),
+            width: Val::Px(style.size),
+            height: Val::Px(style.size),
             position_type: PositionType::Absolute,
             top: Val::Px(5.0),
             right: Val::Px(5.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border_radius: BorderRadius::all(Val::Px(style.corner_radius)),
             ..Default::default()
         },
-        BackgroundColor(bevy_color::palettes::tailwind::RED_400.into()),
+        BackgroundColor(style.color),
+        AccessibilityNode(accessibility),
+        Children::spawn(SpawnIter(style.label.clone().into_iter().map(Text::new))),
     )
 }
 
+#[derive(QueryData)]
+struct CloseableAd {
+    ad_type: &'static MockupAdType,
+    component: &'static MockupAdComponent,
+    chained: Has<MockupChainedReward>,
+    reward_override: Option<&'static MockupRewardOverride>,
+}
+
 fn close_clicked(
     t: On<Pointer<Click>>,
-    q: Query<&ChildOf, With<Button>>,
-    p_q: Query<&MockupAdType>,
+    q: Query<&ChildOf, With<MockupAdCloseButton>>,
+    p_q: Query<CloseableAd>,
     mut ads: MockupAdsSystem,
 ) {
     let Ok(p) = q.get(t.entity) else {
@@ -444,25 +2735,378 @@ fn close_clicked(
     let Ok(ad) = p_q.get(p.0) else {
         return;
     };
-    ads.hide_ad(ad.0);
+    close_ad(ad, &mut ads);
+}
+
+/// Close the ad whose close button is displayed, see [`MockupAds::close_on_escape`].
+fn close_on_escape(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    q: Query<&ChildOf, With<MockupAdCloseButton>>,
+    p_q: Query<CloseableAd>,
+    mut ads: MockupAdsSystem,
+) {
+    if !keys.is_some_and(|keys| keys.just_pressed(KeyCode::Escape)) {
+        return;
+    }
+    if let Some(ad) = q.iter().find_map(|p| p_q.get(p.0).ok()) {
+        close_ad(ad, &mut ads);
+    }
+}
+
+/// Hide an ad closed by the player, granting the early close reward of rewarded ads.
+fn close_ad(
+    CloseableAdItem {
+        ad_type: ad,
+        component,
+        chained,
+        reward_override,
+    }: CloseableAdItem,
+    ads: &mut MockupAdsSystem,
+) {
+    if **ad == AdType::Rewarded && !component.timer.is_finished() {
+        let reward = reward_override
+            .map(|reward| reward.0.clone())
+            .unwrap_or_else(|| ads.r.rewarded_ad_reward.clone());
+        // A chained ad only grants part of what it adds to the reward it replaces.
+        let replaces = match &ads.r.chainable_reward {
+            Some(previous) if chained => previous.amount,
+            _ => 0,
+        };
+        let earnable = reward.amount.saturating_sub(replaces);
+        let amount = match ads.r.rewarded.early_close_reward {
+            EarlyCloseReward::None => 0,
+            EarlyCloseReward::Full => earnable,
+            EarlyCloseReward::Prorated => {
+                (earnable as f32 * component.timer.fraction()).floor() as i32
+            }
+        };
+        // Queued before the ad is despawned, so it precedes its `AdClosed`.
+        if amount > 0 && chained {
+            ads.r.chainable_reward = None;
+            ads.queue.write(AdMessage::ChainedRewardEarned {
+                amount: replaces + amount,
+                reward_type: reward.type_name,
+                replaces,
+            });
+        } else if amount > 0 {
+            ads.queue.write(AdMessage::RewardedAdEarnedReward {
+                amount,
+                reward_type: reward.type_name,
+            });
+        }
+    }
+    ads.hide_ad(**ad);
 }
 
-fn banner_bundle() -> impl Bundle {
+fn native_bundle(layout: &NativeAdLayout, ad_id: &str) -> impl Bundle {
     (
         Node {
-            width: Val::Px(100.0),
-            height: Val::Px(30.0),
-            bottom: Val::Px(0.0),
-            justify_content: JustifyContent::Center,
-            justify_items: JustifyItems::Stretch,
-            align_items: AlignItems::Center,
+            width: Val::Px(layout.width),
+            height: Val::Px(layout.height),
+            left: Val::Percent(50.0),
+            top: Val::Percent(50.0),
+            margin: UiRect {
+                left: Val::Px(-layout.width / 2.0),
+                top: Val::Px(-layout.height / 2.0),
+                ..Default::default()
+            },
+            padding: UiRect::all(Val::Px(10.0)),
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Start,
             flex_direction: FlexDirection::Column,
-            row_gap: Val::Px(10.0),
             position_type: PositionType::Absolute,
+            ..Default::default()
+        },
+        layout.background,
+        MockupAdType::new(AdType::Native, ad_id),
+        MockupAdPendingImpression,
+        children![
+            Text::new(layout.headline.clone()),
+            Text::new(layout.body.clone()),
+            (
+                Button,
+                Node {
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(5.0)),
+                    align_self: AlignSelf::End,
+                    ..Default::default()
+                },
+                BackgroundColor(bevy_color::palettes::tailwind::BLUE_500.into()),
+                children![Text::new(layout.call_to_action.clone())],
+            ),
+        ],
+    )
+}
 
+fn skip_btn(label: &str) -> impl Bundle + use<> {
+    (
+        Button,
+        MockupAdSkipButton,
+        bevy_ui::ZIndex(1),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(5.0),
+            right: Val::Px(5.0),
+            padding: UiRect::all(Val::Px(5.0)),
             ..Default::default()
         },
-        MockupAdType(AdType::Banner),
-        bevy_ui::ZIndex(500),
+        BackgroundColor(bevy_color::palettes::tailwind::ZINC_700.into()),
+        children![Text::new(label)],
+    )
+}
+
+/// Simulate banner refreshes, paused while no banner is displayed.
+fn refresh_banners(
+    mut timer: Local<Option<Timer>>,
+    cfg: Res<MockupAds>,
+    ads: Query<&MockupAdType>,
+    time: Res<Time>,
+    queue: Res<AdEventQueue>,
+) {
+    let Some(interval) = cfg.banner_refresh_interval else {
+        *timer = None;
+        return;
+    };
+    let Some(banner) = ads.iter().find(|ad_type| ***ad_type == AdType::Banner) else {
+        return;
+    };
+    let timer = timer.get_or_insert_with(|| Timer::new(interval, TimerMode::Repeating));
+    if timer.duration() != interval {
+        timer.set_duration(interval);
+    }
+    if timer.tick(time.delta()).just_finished() {
+        queue.write(AdMessage::AdLoaded {
+            ad_type: AdType::Banner,
+            ad_id: banner.ad_id.clone(),
+            network: Some(cfg.fake_network.clone()),
+            personalized: cfg.consent.personalized_ads,
+        });
+    }
+}
+
+/// Emit `BannerSizeChanged` when a banner is shown and whenever its size changes.
+fn report_banner_size(
+    mut reported: Local<Option<(i32, i32)>>,
+    ads: Query<&MockupAdType>,
+    system: MockupAdsSystem,
+) {
+    if !ads.iter().any(|ad_type| **ad_type == AdType::Banner) {
+        *reported = None;
+        return;
+    }
+    let size = (system.get_banner_width(""), system.get_banner_height(""));
+    if *reported == Some(size) {
+        return;
+    }
+    *reported = Some(size);
+    system.queue.write(AdMessage::BannerSizeChanged {
+        width: size.0,
+        height: size.1,
+    });
+}
+
+/// Remove skip buttons of ads that were watched to completion.
+fn remove_skip_buttons(
+    mut commands: Commands,
+    buttons: Query<(Entity, &ChildOf), With<MockupAdSkipButton>>,
+    ads: Query<&MockupAdComponent>,
+) {
+    for (button, parent) in buttons.iter() {
+        if ads
+            .get(parent.0)
+            .is_ok_and(|component| component.timer.is_finished())
+        {
+            commands.entity(button).try_despawn();
+        }
+    }
+}
+
+fn skip_clicked(
+    t: On<Pointer<Click>>,
+    q: Query<&ChildOf, With<MockupAdSkipButton>>,
+    p_q: Query<(&MockupAdType, Option<&MockupAdAnimations>)>,
+    mut commands: Commands,
+    queue: Res<AdEventQueue>,
+) {
+    let Ok(p) = q.get(t.entity) else {
+        return;
+    };
+    let Ok((ad_type, animations)) = p_q.get(p.0) else {
+        return;
+    };
+    commands.entity(t.entity).try_despawn();
+    commands.entity(p.0).try_insert(MockupAdSkipped);
+    queue.write(AdMessage::AdSkipped { ad_type: **ad_type });
+    dismiss_ad(&mut commands, p.0, animations);
+}
+
+fn ad_clicked(
+    t: On<Pointer<Click>>,
+    mut commands: Commands,
+    q: Query<&MockupAdType>,
+    buttons: Query<(Has<MockupAdCloseButton>, Has<MockupAdSkipButton>)>,
+    parents: Query<&ChildOf>,
+    cfg: Res<MockupAds>,
+    queue: Res<AdEventQueue>,
+) {
+    let Ok(ad_type) = q.get(t.entity) else {
+        return;
+    };
+    // Non-clickable banners aren't pickable, this also ignores clicks triggered on them directly.
+    if **ad_type == AdType::Banner && !cfg.banner_clickable {
+        return;
+    }
+    // Closing or skipping the ad, including through the button label, is a separate
+    // interaction from clicking it.
+    let target = t.original_event_target();
+    if std::iter::once(target)
+        .chain(parents.iter_ancestors(target))
+        .take_while(|entity| *entity != t.entity)
+        .any(|entity| buttons.get(entity).is_ok_and(|(close, skip)| close || skip))
+    {
+        return;
+    }
+    queue.write(AdMessage::AdClicked { ad_type: **ad_type });
+    if **ad_type != AdType::Banner {
+        return;
+    }
+    // A banner click opens the ad in a browser, the banner stays displayed.
+    queue.write(AdMessage::AdOpened {
+        ad_type: AdType::Banner,
+        ad_id: ad_type.ad_id.clone(),
+    });
+    if let Some(click_through_ms) = cfg.banner_click_through_ms {
+        commands
+            .entity(t.entity)
+            .insert(MockupBannerClickThrough(Timer::new(
+                Duration::from_millis(click_through_ms),
+                TimerMode::Once,
+            )));
+    }
+}
+
+/// Emit `AdClosed` for banners once the player returned from their click-through.
+fn return_from_banner_click(
+    mut commands: Commands,
+    mut q: Query<(Entity, &MockupAdType, &mut MockupBannerClickThrough)>,
+    time: Res<Time<Real>>,
+    queue: Res<AdEventQueue>,
+) {
+    for (entity, ad_type, mut click_through) in q.iter_mut() {
+        if !click_through.0.tick(time.delta()).just_finished() {
+            continue;
+        }
+        commands.entity(entity).remove::<MockupBannerClickThrough>();
+        queue.write(AdMessage::AdClosed {
+            ad_type: AdType::Banner,
+            ad_id: ad_type.ad_id.clone(),
+            reason: AdCloseReason::Closed,
+        });
+    }
+}
+
+fn retry_load(t: On<AdLoadRetry>, mut ads: MockupAdsSystem) {
+    // Loading resets the retries, a retry keeps counting them.
+    let retry = ads.retries.0.remove(&t.ad_type);
+    ads.load_ad(t.ad_type, &t.ad_id);
+    if let Some(retry) = retry {
+        ads.retries.0.insert(t.ad_type, retry);
+    }
+}
+
+fn opt_in_answered(
+    t: On<Pointer<Click>>,
+    q: Query<&MockupOptInAnswer>,
+    prompts: Query<&MockupOptInPrompt>,
+    mut ads: MockupAdsSystem,
+) {
+    let Ok(answer) = q.get(t.entity) else {
+        return;
+    };
+    let Ok(prompt) = prompts.get(answer.prompt) else {
+        return;
+    };
+    let reward = prompt.0.clone();
+    ads.cmd.entity(answer.prompt).try_despawn();
+    if answer.accept {
+        ads.play_rewarded(reward);
+        return;
+    }
+    let ad_id = ads
+        .timer
+        .ad_id(AdType::Rewarded)
+        .unwrap_or_default()
+        .to_string();
+    ads.timer.consume(AdType::Rewarded);
+    ads.queue.write(AdMessage::AdClosed {
+        ad_type: AdType::Rewarded,
+        ad_id,
+        reason: AdCloseReason::UserDismissed,
+    });
+}
+
+fn position_banners(In(position): In<BannerPosition>, mut q: Query<(&mut Node, &MockupAdType)>) {
+    for (mut node, ad_type) in q.iter_mut() {
+        if **ad_type != AdType::Banner {
+            continue;
+        }
+        let Val::Px(width) = node.width else {
+            continue;
+        };
+        apply_banner_position(&mut node, position, width);
+    }
+}
+
+fn apply_banner_position(node: &mut Node, position: BannerPosition, width: f32) {
+    node.top = Val::Auto;
+    node.bottom = Val::Auto;
+    node.left = Val::Auto;
+    node.right = Val::Auto;
+    node.margin = UiRect::default();
+    match position {
+        BannerPosition::Top | BannerPosition::TopLeft | BannerPosition::TopRight => {
+            node.top = Val::Px(0.0);
+        }
+        BannerPosition::Bottom | BannerPosition::BottomLeft | BannerPosition::BottomRight => {
+            node.bottom = Val::Px(0.0);
+        }
+        BannerPosition::Custom { top, left } => {
+            node.top = Val::Px(top);
+            node.left = Val::Px(left);
+        }
+    }
+    match position {
+        BannerPosition::Top | BannerPosition::Bottom => {
+            node.left = Val::Percent(50.0);
+            node.margin.left = Val::Px(-width / 2.0);
+        }
+        BannerPosition::TopLeft | BannerPosition::BottomLeft => {
+            node.left = Val::Px(0.0);
+        }
+        BannerPosition::TopRight | BannerPosition::BottomRight => {
+            node.right = Val::Px(0.0);
+        }
+        BannerPosition::Custom { .. } => {}
+    }
+}
+
+fn banner_bundle(width: f32, height: f32, position: BannerPosition, ad_id: &str) -> impl Bundle {
+    let mut node = Node {
+        width: Val::Px(width),
+        height: Val::Px(height),
+        justify_content: JustifyContent::Center,
+        justify_items: JustifyItems::Stretch,
+        align_items: AlignItems::Center,
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(10.0),
+        position_type: PositionType::Absolute,
+
+        ..Default::default()
+    };
+    apply_banner_position(&mut node, position, width);
+    (
+        node,
+        Button,
+        MockupAdType::new(AdType::Banner, ad_id),
+        MockupAdPendingImpression,
     )
 }
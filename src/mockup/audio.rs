@@ -0,0 +1,54 @@
+//! Audio cues played by the mockup on ad events.
+
+use bevy_app::{App, Update};
+use bevy_asset::Handle;
+use bevy_audio::{AudioPlayer, AudioSource, PlaybackSettings, Volume};
+use bevy_ecs::{
+    message::MessageReader,
+    system::{Commands, Res},
+};
+use bevy_reflect::Reflect;
+
+use super::MockupAds;
+use crate::AdMessage;
+
+/// Sounds played by the mockup, `None` plays nothing.
+#[derive(Debug, Default, Reflect, Clone)]
+pub struct MockupAudioCues {
+    /// Played when an ad finishes loading.
+    pub loaded: Option<Handle<AudioSource>>,
+    /// Played when an ad appears on screen.
+    pub opened: Option<Handle<AudioSource>>,
+    /// Played when a rewarded ad grants its reward.
+    pub reward: Option<Handle<AudioSource>>,
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_systems(Update, play_audio_cues);
+}
+
+fn play_audio_cues(
+    mut messages: MessageReader<AdMessage>,
+    cfg: Res<MockupAds>,
+    mut commands: Commands,
+) {
+    for message in messages.read() {
+        if cfg.muted {
+            continue;
+        }
+        let cue = match message {
+            AdMessage::AdLoaded { .. } => &cfg.audio_cues.loaded,
+            AdMessage::AdImpression { .. } => &cfg.audio_cues.opened,
+            AdMessage::RewardedAdEarnedReward { .. } | AdMessage::ChainedRewardEarned { .. } => {
+                &cfg.audio_cues.reward
+            }
+            _ => continue,
+        };
+        if let Some(handle) = cue {
+            commands.spawn((
+                AudioPlayer::new(handle.clone()),
+                PlaybackSettings::DESPAWN.with_volume(Volume::Linear(cfg.volume)),
+            ));
+        }
+    }
+}
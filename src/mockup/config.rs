@@ -0,0 +1,185 @@
+//! Mockup configuration loaded from a RON asset, applied again whenever it is hot-reloaded.
+//! Requires the `AssetPlugin`, set [`crate::AdsCommonPlugin::mockup_config`] to load it on startup.
+
+use bevy_app::{App, Startup, Update};
+use bevy_asset::{
+    Asset, AssetApp, AssetEvent, AssetLoader, AssetServer, Assets, Handle, LoadContext, io::Reader,
+};
+use bevy_ecs::{
+    message::MessageReader,
+    resource::Resource,
+    system::{Commands, Res, ResMut},
+};
+use bevy_reflect::TypePath;
+use serde::{Deserialize, Serialize};
+
+use super::{AdDisplay, AdDisplaySettings, ImageFit, MockupAdNetwork, MockupAds, MockupFakeLoader};
+use crate::{BannerSize, Reward};
+
+/// Tunable subset of [`MockupAds`], fields left out keep their current value.
+/// Loaded from `*.ads.ron` files.
+#[derive(Asset, TypePath, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MockupAdsConfig {
+    pub loading_time_ms: Option<u64>,
+    pub interstitial: Option<AdDisplayConfig>,
+    pub rewarded: Option<AdDisplayConfig>,
+    pub app_open: Option<AdDisplayConfig>,
+    pub rewarded_ad_reward: Option<Reward>,
+    pub banner_size: Option<BannerSize>,
+    pub waterfall: Option<Vec<MockupAdNetwork>>,
+    pub fake_ecpm_micros: Option<i64>,
+}
+
+/// Tunable subset of [`AdDisplaySettings`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdDisplayConfig {
+    pub show_time_left: Option<bool>,
+    pub auto_close: Option<bool>,
+    pub auto_close_after_reward: Option<bool>,
+    pub opt_in_prompt: Option<bool>,
+    pub duration_ms: Option<u64>,
+    /// Asset path of an image displayed as [`AdDisplay::Image`].
+    pub image: Option<String>,
+    /// Fit of [`AdDisplayConfig::image`], defaults to [`ImageFit::Cover`].
+    pub image_fit: Option<ImageFit>,
+}
+
+impl AdDisplayConfig {
+    fn apply(&self, settings: &mut AdDisplaySettings, asset_server: &AssetServer) {
+        if let Some(show_time_left) = self.show_time_left {
+            settings.show_time_left = show_time_left;
+        }
+        if let Some(auto_close) = self.auto_close {
+            settings.auto_close = auto_close;
+        }
+        if let Some(auto_close_after_reward) = self.auto_close_after_reward {
+            settings.auto_close_after_reward = auto_close_after_reward;
+        }
+        if let Some(opt_in_prompt) = self.opt_in_prompt {
+            settings.opt_in_prompt = opt_in_prompt;
+        }
+        if let Some(duration_ms) = self.duration_ms {
+            settings.duration_ms = duration_ms;
+        }
+        if let Some(image) = &self.image {
+            settings.display = AdDisplay::Image {
+                handle: asset_server.load(image.clone()),
+                fit: self.image_fit.unwrap_or_default(),
+            };
+        }
+    }
+}
+
+impl MockupAdsConfig {
+    /// Apply the configured fields onto `ads`, loading the configured images.
+    pub fn apply(&self, ads: &mut MockupAds, asset_server: &AssetServer) {
+        if let Some(loading_time_ms) = self.loading_time_ms {
+            ads.loading_time_ms = loading_time_ms;
+        }
+        if let Some(interstitial) = &self.interstitial {
+            interstitial.apply(&mut ads.interstitial, asset_server);
+        }
+        if let Some(rewarded) = &self.rewarded {
+            rewarded.apply(&mut ads.rewarded, asset_server);
+        }
+        if let Some(app_open) = &self.app_open {
+            app_open.apply(&mut ads.app_open, asset_server);
+        }
+        if let Some(reward) = &self.rewarded_ad_reward {
+            ads.rewarded_ad_reward = reward.clone();
+        }
+        if let Some(banner_size) = self.banner_size {
+            ads.banner_size = banner_size;
+        }
+        if let Some(waterfall) = &self.waterfall {
+            ads.waterfall = waterfall.clone();
+        }
+        if let Some(fake_ecpm_micros) = self.fake_ecpm_micros {
+            ads.fake_ecpm_micros = fake_ecpm_micros;
+        }
+    }
+}
+
+/// Config asset applied to [`MockupAds`] once loaded and on every reload.
+#[derive(Resource, Debug, Clone)]
+pub struct MockupAdsConfigHandle(pub Handle<MockupAdsConfig>);
+
+#[derive(Debug, thiserror::Error)]
+pub enum MockupAdsConfigLoaderError {
+    #[error("Could not read the config: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse the config: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+}
+
+#[derive(TypePath, Default)]
+pub struct MockupAdsConfigLoader;
+
+impl AssetLoader for MockupAdsConfigLoader {
+    type Asset = MockupAdsConfig;
+    type Settings = ();
+    type Error = MockupAdsConfigLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ads.ron"]
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.init_asset::<MockupAdsConfig>()
+        .init_asset_loader::<MockupAdsConfigLoader>()
+        .add_systems(Update, apply_config);
+}
+
+/// Load the config at `path` on startup.
+pub(crate) fn load_on_startup(app: &mut App, path: String) {
+    app.add_systems(
+        Startup,
+        move |mut commands: Commands, asset_server: Res<AssetServer>| {
+            commands.insert_resource(MockupAdsConfigHandle(asset_server.load(path.clone())));
+        },
+    );
+}
+
+fn apply_config(
+    mut events: MessageReader<AssetEvent<MockupAdsConfig>>,
+    configs: Res<Assets<MockupAdsConfig>>,
+    handle: Option<Res<MockupAdsConfigHandle>>,
+    asset_server: Res<AssetServer>,
+    mut ads: ResMut<MockupAds>,
+    mut loader: ResMut<MockupFakeLoader>,
+) {
+    let Some(handle) = handle else {
+        events.clear();
+        return;
+    };
+    let mut changed = false;
+    for event in events.read() {
+        changed |= event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0);
+    }
+    if !changed {
+        return;
+    }
+    let Some(config) = configs.get(&handle.0) else {
+        return;
+    };
+    let loading_time_ms = ads.loading_time_ms;
+    config.apply(&mut ads, &asset_server);
+    if ads.loading_time_ms != loading_time_ms {
+        loader.set_duration(std::time::Duration::from_millis(ads.loading_time_ms));
+    }
+    bevy_log::info!("Applied mockup ads config");
+}
@@ -0,0 +1,68 @@
+//! On screen log of ad events, e.g. for debugging an ads integration.
+
+use std::collections::VecDeque;
+
+use bevy_app::{App, Update};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_time::{Real, Time};
+use bevy_ui::widget::Text;
+
+use crate::AdMessage;
+
+/// Displays the latest ad events in its [`Text`], newest first.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+#[require(Text)]
+pub struct AdEventLog {
+    /// Number of events kept, older events are dropped.
+    pub max_len: usize,
+    /// Formatted events, oldest first.
+    pub entries: VecDeque<String>,
+}
+
+impl AdEventLog {
+    /// Log keeping the latest `max_len` events.
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            max_len,
+            entries: VecDeque::with_capacity(max_len),
+        }
+    }
+}
+
+impl Default for AdEventLog {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<AdEventLog>()
+        .add_systems(Update, update_event_logs);
+}
+
+fn update_event_logs(
+    mut messages: MessageReader<AdMessage>,
+    mut q: Query<(&mut AdEventLog, &mut Text)>,
+    time: Res<Time<Real>>,
+) {
+    let new: Vec<String> = messages
+        .read()
+        .map(|message| format!("{:.2}s: {message:?}", time.elapsed_secs()))
+        .collect();
+    if new.is_empty() {
+        return;
+    }
+    for (mut log, mut text) in q.iter_mut() {
+        log.entries.extend(new.iter().cloned());
+        let excess = log.entries.len().saturating_sub(log.max_len);
+        log.entries.drain(..excess);
+        text.0.clear();
+        text.0.push_str("Ads events:\n");
+        for entry in log.entries.iter().rev() {
+            text.0.push_str(entry);
+            text.0.push('\n');
+        }
+    }
+}
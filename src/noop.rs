@@ -0,0 +1,72 @@
+//! Ads implementation that does nothing, for builds with ads disabled.
+
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::SystemParam;
+
+use crate::{AdEventQueue, AdManager, AdMessage};
+
+/// Drop-in [`AdManager`] for builds with ads disabled, e.g. a paid version.
+/// It is always initialized, never shows an ad and hiding always succeeds.
+#[derive(SystemParam)]
+pub struct NoopAdManager<'w> {
+    queue: Res<'w, AdEventQueue>,
+}
+
+impl AdManager for NoopAdManager<'_> {
+    fn initialize(&mut self) -> bool {
+        self.queue.write(AdMessage::Initialized { success: true });
+        true
+    }
+
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn show_banner(&mut self) -> bool {
+        false
+    }
+
+    fn show_interstitial(&mut self) -> bool {
+        false
+    }
+
+    fn show_rewarded(&mut self) -> bool {
+        false
+    }
+
+    fn hide_banner(&mut self) -> bool {
+        true
+    }
+
+    fn hide_interstitial(&mut self) -> bool {
+        true
+    }
+
+    fn hide_rewarded(&mut self) -> bool {
+        true
+    }
+
+    fn hide_app_open(&mut self) -> bool {
+        true
+    }
+
+    fn hide_native(&mut self) -> bool {
+        true
+    }
+
+    fn load_banner(&mut self, _ad_id: &str) -> bool {
+        false
+    }
+
+    fn load_interstitial(&mut self, _ad_id: &str) -> bool {
+        false
+    }
+
+    fn load_rewarded(&mut self, _ad_id: &str) -> bool {
+        false
+    }
+
+    fn consent_required_in_region(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,132 @@
+//! Opt-in retry of failed ad loads with exponential backoff.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy_app::{App, Update};
+use bevy_ecs::prelude::*;
+use bevy_platform::time::Instant;
+use bevy_reflect::prelude::*;
+
+use crate::{AdMessage, AdType};
+
+/// Retry failed ad loads, retries are enabled only while this resource is present.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first failure.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub base_backoff_ms: u64,
+    /// Factor the delay grows by with every retry.
+    pub multiplier: f32,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_backoff_ms: 1000,
+            multiplier: 2.0,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the given retry, starting at 1.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        Duration::from_secs_f64(self.base_backoff_ms as f64 / 1000.0 * factor as f64)
+    }
+}
+
+/// Retries of a single ad type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdRetry {
+    /// Ad unit id of the failed load.
+    pub ad_id: String,
+    /// Number of retries issued so far.
+    pub attempts: u32,
+    /// When the next retry is issued, `None` once the retries are exhausted.
+    pub next_retry: Option<Instant>,
+}
+
+/// Retries in progress per ad type, cleared once the ad loads.
+/// Ads implementations also [`AdRetryState::reset`] it when the game requests a load.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct AdRetryState(pub HashMap<AdType, AdRetry>);
+
+impl AdRetryState {
+    /// Forget the retries of this ad type, so that the next failure starts them over.
+    pub fn reset(&mut self, ad_type: AdType) {
+        self.0.remove(&ad_type);
+    }
+}
+
+/// Triggered when a failed ad load should be retried.
+/// Ads implementations observe it and load the ad again.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct AdLoadRetry {
+    pub ad_type: AdType,
+    pub ad_id: String,
+    /// Retry number, starting at 1.
+    pub attempt: u32,
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<RetryPolicy>()
+        .init_resource::<AdRetryState>()
+        .add_systems(
+            Update,
+            (track_failures, issue_retries)
+                .chain()
+                .run_if(resource_exists::<RetryPolicy>),
+        );
+}
+
+fn track_failures(
+    mut messages: MessageReader<AdMessage>,
+    policy: Res<RetryPolicy>,
+    mut state: ResMut<AdRetryState>,
+) {
+    for message in messages.read() {
+        match message {
+            AdMessage::AdLoaded { ad_type, .. } => {
+                state.0.remove(ad_type);
+            }
+            AdMessage::AdFailedToLoad { ad_type, ad_id, .. } => {
+                let retry = state.0.entry(*ad_type).or_insert_with(|| AdRetry {
+                    ad_id: ad_id.clone(),
+                    attempts: 0,
+                    next_retry: None,
+                });
+                retry.ad_id.clone_from(ad_id);
+                retry.next_retry = (retry.attempts < policy.max_attempts)
+                    .then(|| Instant::now() + policy.backoff(retry.attempts + 1));
+                if retry.next_retry.is_none() {
+                    bevy_log::warn!(
+                        "Giving up loading {ad_type} ad after {} retries",
+                        retry.attempts
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn issue_retries(mut commands: Commands, mut state: ResMut<AdRetryState>) {
+    let now = Instant::now();
+    for (ad_type, retry) in state.0.iter_mut() {
+        if retry.next_retry.is_none_or(|at| at > now) {
+            continue;
+        }
+        retry.attempts += 1;
+        retry.next_retry = None;
+        commands.trigger(AdLoadRetry {
+            ad_type: *ad_type,
+            ad_id: retry.ad_id.clone(),
+            attempt: retry.attempts,
+        });
+    }
+}
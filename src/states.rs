@@ -0,0 +1,116 @@
+//! Ads lifecycle exposed as Bevy [`States`], e.g. to pause gameplay while a fullscreen ad is up.
+
+use std::collections::{HashMap, HashSet};
+
+use bevy_app::{App, Update};
+use bevy_ecs::prelude::*;
+use bevy_platform::time::Instant;
+use bevy_reflect::prelude::*;
+use bevy_state::prelude::*;
+
+use crate::{AdMessage, AdType};
+
+/// Log target of the [`AdLifecycle`] transitions, logged at info level with the `ad_type`
+/// causing them, the `from` and `to` states and the milliseconds spent in the previous
+/// state as `elapsed_ms`. Filter it out to disable them, e.g. `bevy_ads_common::lifecycle=warn`.
+pub const LIFECYCLE_LOG_TARGET: &str = "bevy_ads_common::lifecycle";
+
+/// State of the fullscreen ads, maintained from the [`AdMessage`]s.
+/// Banner and native ads don't affect it.
+/// Loads discarded without a message, e.g. with [`AdManager::destroy_ad`](crate::AdManager::destroy_ad),
+/// are still counted as loaded.
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum AdLifecycle {
+    /// No fullscreen ad is loaded or displayed.
+    #[default]
+    Idle,
+    /// A fullscreen ad of any type is loaded and none is displayed.
+    Ready,
+    /// A fullscreen ad is displayed.
+    Showing,
+}
+
+pub(crate) fn plugin(app: &mut App) {
+    app.init_state::<AdLifecycle>()
+        .register_type::<AdLifecycle>()
+        .add_systems(Update, track_lifecycle);
+}
+
+/// Fullscreen ads the lifecycle is derived from.
+#[derive(Default)]
+struct TrackedAds {
+    /// Loaded ads per ad type that weren't displayed yet.
+    loaded: HashMap<AdType, u32>,
+    /// Ad types with a displayed ad.
+    showing: HashSet<AdType>,
+}
+
+impl TrackedAds {
+    fn use_load(&mut self, ad_type: AdType) {
+        if let Some(loaded) = self.loaded.get_mut(&ad_type) {
+            *loaded = loaded.saturating_sub(1);
+        }
+    }
+
+    fn lifecycle(&self) -> AdLifecycle {
+        if !self.showing.is_empty() {
+            AdLifecycle::Showing
+        } else if self.loaded.values().any(|loaded| *loaded > 0) {
+            AdLifecycle::Ready
+        } else {
+            AdLifecycle::Idle
+        }
+    }
+}
+
+fn track_lifecycle(
+    mut messages: MessageReader<AdMessage>,
+    mut tracked: Local<TrackedAds>,
+    mut last_transition: Local<Option<Instant>>,
+    state: Res<State<AdLifecycle>>,
+    mut next_state: ResMut<NextState<AdLifecycle>>,
+) {
+    // The first state lasts from the first run.
+    let entered = last_transition.get_or_insert_with(Instant::now);
+    let mut lifecycle = tracked.lifecycle();
+    for message in messages.read() {
+        let Some(ad_type) = message
+            .ad_type()
+            .filter(|ad_type| !matches!(ad_type, AdType::Banner | AdType::Native))
+        else {
+            continue;
+        };
+        match message {
+            AdMessage::AdLoaded { .. } => *tracked.loaded.entry(ad_type).or_default() += 1,
+            AdMessage::AdOpened { .. } => {
+                tracked.use_load(ad_type);
+                tracked.showing.insert(ad_type);
+            }
+            AdMessage::AdClosed { .. } => {
+                // A loaded ad skipped before it was displayed is closed without opening.
+                if !tracked.showing.remove(&ad_type) {
+                    tracked.use_load(ad_type);
+                }
+            }
+            _ => {}
+        }
+        let from = lifecycle;
+        lifecycle = tracked.lifecycle();
+        if lifecycle == from {
+            continue;
+        }
+        let now = Instant::now();
+        let elapsed = now.duration_since(std::mem::replace(entered, now));
+        bevy_log::info!(
+            target: LIFECYCLE_LOG_TARGET,
+            ad_type = %ad_type,
+            from = ?from,
+            to = ?lifecycle,
+            elapsed_ms = elapsed.as_millis() as u64,
+            "Ad lifecycle transition"
+        );
+    }
+    if lifecycle != **state {
+        next_state.set(lifecycle);
+    }
+}
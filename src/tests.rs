@@ -0,0 +1,213 @@
+//! Behaviour checks driven through a headless app.
+
+use std::any::TypeId;
+use std::time::Duration;
+
+use bevy::MinimalPlugins;
+use bevy_app::{App, Update};
+use bevy_ecs::prelude::*;
+use bevy_ecs::reflect::AppTypeRegistry;
+
+use crate::{
+    AdEventQueue, AdEventsDrained, AdMessage, AdSystems, AdType, AdsCommonPlugin, DrainSchedule,
+    PendingRewards, Reward,
+};
+
+#[cfg(feature = "mockup")]
+mod mockup;
+
+/// Messages written since the last [`take_written`].
+#[derive(Resource, Default)]
+struct Written(Vec<AdMessage>);
+
+fn record(mut messages: MessageReader<AdMessage>, mut written: ResMut<Written>) {
+    written.0.extend(messages.read().cloned());
+}
+
+/// App draining the event queue in `Update`, so every update delivers the queued events.
+fn app_with(plugin: AdsCommonPlugin) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins);
+    // The mockup displays image assets.
+    #[cfg(feature = "mockup")]
+    app.add_plugins(bevy_asset::AssetPlugin::default());
+    // The lifecycle state needs the state transitions.
+    #[cfg(feature = "states")]
+    app.add_plugins(bevy_state::app::StatesPlugin);
+    app.add_plugins(plugin.drain_schedule(DrainSchedule::Update))
+        .init_resource::<Written>()
+        .add_systems(Update, record.after(AdSystems::DrainQueue));
+    app
+}
+
+fn take_written(app: &mut App) -> Vec<AdMessage> {
+    std::mem::take(&mut app.world_mut().resource_mut::<Written>().0)
+}
+
+#[test]
+fn drain_reports_the_event_count() {
+    #[derive(Resource, Default)]
+    struct Drained(Vec<usize>);
+
+    let mut app = app_with(AdsCommonPlugin::default());
+    app.init_resource::<Drained>().add_observer(
+        |t: On<AdEventsDrained>, mut drained: ResMut<Drained>| {
+            drained.0.push(t.count);
+        },
+    );
+    app.update();
+    app.world_mut().resource_mut::<Drained>().0.clear();
+    let queue = app.world().resource::<AdEventQueue>().clone();
+    for _ in 0..3 {
+        queue.write(AdMessage::AdClicked {
+            ad_type: AdType::Banner,
+        });
+    }
+    app.update();
+    app.update();
+    assert_eq!(app.world().resource::<Drained>().0, [3, 0]);
+}
+
+#[test]
+fn stale_events_are_dropped() {
+    let mut app =
+        app_with(AdsCommonPlugin::default().max_event_age(Some(Duration::from_millis(20))));
+    app.update();
+    take_written(&mut app);
+    let queue = app.world().resource::<AdEventQueue>().clone();
+    let clicked = |ad_type| AdMessage::AdClicked { ad_type };
+    queue.write(clicked(AdType::Banner));
+    queue.write(clicked(AdType::Banner));
+    // The app is paused for longer than the maximum age.
+    std::thread::sleep(Duration::from_millis(50));
+    queue.write(clicked(AdType::Interstitial));
+    app.update();
+    let written = take_written(&mut app);
+    assert_eq!(written.len(), 1);
+    assert!(matches!(
+        written[0],
+        AdMessage::AdClicked {
+            ad_type: AdType::Interstitial
+        }
+    ));
+}
+
+#[test]
+fn earned_rewards_stay_pending_until_taken() {
+    let mut app = app_with(AdsCommonPlugin::default());
+    app.update();
+    let queue = app.world().resource::<AdEventQueue>().clone();
+    queue.write(AdMessage::RewardedAdEarnedReward {
+        amount: 5,
+        reward_type: "coins".to_string(),
+    });
+    for _ in 0..5 {
+        app.update();
+    }
+    let rewards = app
+        .world_mut()
+        .resource_mut::<PendingRewards>()
+        .take_pending_rewards();
+    assert_eq!(
+        rewards,
+        [Reward {
+            amount: 5,
+            type_name: "coins".to_string(),
+        }]
+    );
+    assert!(app.world().resource::<PendingRewards>().0.is_empty());
+}
+
+#[cfg(feature = "diagnostics")]
+#[test]
+fn system_timings_are_recorded() {
+    use bevy_diagnostic::DiagnosticsStore;
+
+    let mut app = app_with(AdsCommonPlugin::default());
+    for _ in 0..3 {
+        app.update();
+    }
+    let store = app.world().resource::<DiagnosticsStore>();
+    let paths = [
+        crate::diagnostics::HANDLE_EVENTS,
+        #[cfg(feature = "mockup")]
+        crate::diagnostics::MOCKUP_SHOW_ADS,
+        #[cfg(feature = "mockup")]
+        crate::diagnostics::MOCKUP_FAKE_LOADER,
+    ];
+    for path in paths {
+        let diagnostic = store.get(&path).unwrap();
+        assert!(diagnostic.measurement().is_some(), "{path} wasn't measured");
+    }
+}
+
+#[test]
+fn ad_types_are_registered_for_reflection() {
+    let app = app_with(AdsCommonPlugin::default());
+    let registry = app.world().resource::<AppTypeRegistry>().read();
+    assert!(registry.contains(TypeId::of::<AdType>()));
+    #[cfg(feature = "mockup")]
+    for type_id in [
+        TypeId::of::<Reward>(),
+        TypeId::of::<crate::prelude::AdDisplay>(),
+        TypeId::of::<crate::prelude::AdDisplaySettings>(),
+    ] {
+        assert!(registry.contains(type_id));
+    }
+}
+
+#[cfg(feature = "states")]
+#[test]
+fn lifecycle_transitions_are_logged() {
+    use std::sync::{Arc, Mutex};
+
+    use bevy_ecs::schedule::ExecutorKind;
+    use bevy_log::tracing::field::{Field, Visit};
+    use bevy_log::tracing::{Event, Subscriber};
+    use bevy_log::tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+    use bevy_log::tracing_subscriber::registry::Registry;
+
+    /// States logged as `to` by the lifecycle transitions.
+    #[derive(Clone, Default)]
+    struct Transitions(Arc<Mutex<Vec<String>>>);
+
+    impl<S: Subscriber> Layer<S> for Transitions {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            struct To<'a>(&'a mut Vec<String>);
+            impl Visit for To<'_> {
+                fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                    if field.name() == "to" {
+                        self.0.push(format!("{value:?}"));
+                    }
+                }
+            }
+            if event.metadata().target() == crate::LIFECYCLE_LOG_TARGET {
+                event.record(&mut To(&mut self.0.lock().unwrap()));
+            }
+        }
+    }
+
+    let transitions = Transitions::default();
+    let subscriber = Registry::default().with(transitions.clone());
+    bevy_log::tracing::subscriber::with_default(subscriber, || {
+        let mut app = app_with(AdsCommonPlugin::default());
+        // The subscriber is set for this thread only.
+        app.edit_schedule(Update, |schedule| {
+            schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+        });
+        let queue = app.world().resource::<AdEventQueue>().clone();
+        queue.write(AdMessage::AdLoaded {
+            ad_type: AdType::Interstitial,
+            ad_id: "test".to_string(),
+            network: None,
+            personalized: false,
+        });
+        queue.write(AdMessage::AdOpened {
+            ad_type: AdType::Interstitial,
+            ad_id: "test".to_string(),
+        });
+        app.update();
+        app.update();
+    });
+    assert_eq!(*transitions.0.lock().unwrap(), ["Ready", "Showing"]);
+}
@@ -0,0 +1,1142 @@
+use std::time::Duration;
+
+use bevy::camera::NormalizedRenderTarget;
+use bevy_app::{App, Update};
+use bevy_asset::Handle;
+use bevy_color::{Alpha, Color};
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::RunSystemOnce;
+use bevy_math::Vec2;
+use bevy_picking::Pickable;
+use bevy_picking::backend::HitData;
+use bevy_picking::events::{Click, Pointer};
+use bevy_picking::pointer::{Location, PointerButton, PointerId};
+use bevy_time::{TimeUpdateStrategy, Timer, TimerMode};
+use bevy_ui::{BackgroundColor, ComputedNode, Node, Val, widget::Text};
+
+use super::{app_with, take_written};
+use crate::prelude::*;
+use crate::{AdRetryState, BannerSize};
+
+/// Length of a frame, every update advances the time by it.
+const FRAME: Duration = Duration::from_millis(100);
+
+/// Initialized mockup app.
+fn mockup_app() -> App {
+    let mut app = app_with(AdsCommonPlugin::default());
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+    app.update();
+    take_written(&mut app);
+    app
+}
+
+/// Call the mockup ads manager, applying its commands right away.
+fn ads<T: Send + 'static>(
+    app: &mut App,
+    f: impl FnOnce(&mut MockupAdsSystem) -> T + Send + Sync + 'static,
+) -> T {
+    let mut f = Some(f);
+    app.world_mut()
+        .run_system_once(move |mut ads: MockupAdsSystem| f.take().unwrap()(&mut ads))
+        .unwrap()
+}
+
+/// Load an ad of this type, updating until it is ready. Returns the messages written meanwhile.
+fn load(app: &mut App, ad_type: AdType) -> Vec<AdMessage> {
+    assert!(ads(app, move |ads| ads.load_ad(ad_type, "test")));
+    for _ in 0..100 {
+        app.update();
+        if ads(app, move |ads| ads.is_ad_ready(ad_type)) {
+            app.update();
+            return take_written(app);
+        }
+    }
+    panic!("{ad_type} ad didn't load");
+}
+
+/// Update until `time` has passed.
+fn advance(app: &mut App, time: Duration) {
+    for _ in 0..time.div_duration_f32(FRAME).ceil() as u32 {
+        app.update();
+    }
+}
+
+/// Amounts of the rewards earned since the last [`take_written`].
+fn earned(app: &mut App) -> Vec<i32> {
+    take_written(app)
+        .into_iter()
+        .filter_map(|message| match message {
+            AdMessage::RewardedAdEarnedReward { amount, .. } => Some(amount),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Click `entity` like a pointer would, the click bubbles up to its ancestors.
+fn click(app: &mut App, entity: Entity) {
+    let location = Location {
+        target: NormalizedRenderTarget::None {
+            width: 1,
+            height: 1,
+        },
+        position: Vec2::ZERO,
+    };
+    let click = Click {
+        button: PointerButton::Primary,
+        hit: HitData::new(Entity::PLACEHOLDER, 0.0, None, None),
+        duration: Duration::ZERO,
+    };
+    app.world_mut()
+        .trigger(Pointer::new(PointerId::Mouse, location, click, entity));
+    app.world_mut().flush();
+}
+
+/// Ad types of the displayed mockup ads.
+fn displayed(app: &mut App) -> Vec<AdType> {
+    let world = app.world_mut();
+    world
+        .query::<&MockupAdType>()
+        .iter(world)
+        .map(|ad_type| **ad_type)
+        .collect()
+}
+
+#[test]
+fn consent_is_stored_per_purpose() {
+    let mut app = mockup_app();
+    let state = ConsentState {
+        storage: true,
+        analytics: false,
+        personalized_ads: false,
+    };
+    ads(&mut app, move |ads| ads.set_consent(state));
+    app.update();
+    assert_eq!(ads(&mut app, |ads| ads.consent_state()), state);
+    assert!(take_written(&mut app).iter().any(|message| matches!(
+        message,
+        AdMessage::ConsentGathered { state: gathered, .. } if *gathered == state
+    )));
+}
+
+#[test]
+fn loads_follow_personalized_consent() {
+    let mut app = mockup_app();
+    let personalized = |messages: Vec<AdMessage>| {
+        messages.iter().find_map(|message| match message {
+            AdMessage::AdLoaded { personalized, .. } => Some(*personalized),
+            _ => None,
+        })
+    };
+    let state = ConsentState {
+        storage: true,
+        analytics: true,
+        personalized_ads: false,
+    };
+    ads(&mut app, move |ads| ads.set_consent(state));
+    assert_eq!(
+        personalized(load(&mut app, AdType::Interstitial)),
+        Some(false)
+    );
+    let state = ConsentState {
+        personalized_ads: true,
+        ..state
+    };
+    ads(&mut app, move |ads| ads.set_consent(state));
+    assert_eq!(personalized(load(&mut app, AdType::Rewarded)), Some(true));
+}
+
+#[test]
+fn interstitial_shows_on_every_nth_trigger() {
+    let mut app = mockup_app();
+    load(&mut app, AdType::Interstitial);
+    let every = app.world().resource::<InterstitialPacing>().every;
+    let mut opened = 0;
+    for _ in 0..every {
+        assert!(displayed(&mut app).is_empty());
+        app.world_mut().trigger(InterstitialTrigger);
+        app.update();
+        opened += take_written(&mut app)
+            .iter()
+            .filter(|message| matches!(message, AdMessage::AdOpened { .. }))
+            .count();
+    }
+    assert_eq!(opened, 1);
+    assert_eq!(displayed(&mut app), [AdType::Interstitial]);
+}
+
+#[test]
+fn chained_reward_replaces_the_previous_one_doubled() {
+    let mut app = mockup_app();
+    let duration = Duration::from_millis(app.world().resource::<MockupAds>().rewarded.duration_ms);
+    app.world_mut()
+        .resource_mut::<MockupAds>()
+        .rewarded_ad_reward = Reward {
+        amount: 10,
+        type_name: "coins".to_string(),
+    };
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_rewarded()));
+    advance(&mut app, duration + FRAME);
+    assert_eq!(earned(&mut app), [10]);
+    ads(&mut app, |ads| ads.hide_rewarded());
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_chained_rewarded()));
+    advance(&mut app, duration + FRAME);
+    let chained: Vec<_> = take_written(&mut app)
+        .into_iter()
+        .filter_map(|message| match message {
+            AdMessage::ChainedRewardEarned {
+                amount, replaces, ..
+            } => Some((amount, replaces)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(chained, [(20, 10)]);
+    // Only the difference to the replaced reward is still owed.
+    let pending = app
+        .world_mut()
+        .resource_mut::<PendingRewards>()
+        .take_pending_rewards();
+    assert_eq!(
+        pending.iter().map(|r| r.amount).collect::<Vec<_>>(),
+        [10, 10]
+    );
+    assert!(!ads(&mut app, |ads| ads.can_chain_reward()));
+}
+
+#[test]
+fn snapshot_restores_into_a_fresh_app() {
+    let mut app = mockup_app();
+    app.insert_resource(FrequencyCap::default());
+    ads(&mut app, |ads| {
+        ads.set_consent(ConsentState {
+            storage: true,
+            ..Default::default()
+        })
+    });
+    app.world_mut()
+        .resource_mut::<MockupAds>()
+        .post_rewarded_cooldown = Duration::from_secs(30);
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    ads(&mut app, |ads| ads.hide_interstitial());
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_rewarded()));
+    app.update();
+    ads(&mut app, |ads| ads.hide_rewarded());
+    app.world_mut().trigger(InterstitialTrigger);
+    ads(&mut app, |ads| ads.set_ads_enabled(false));
+    app.update();
+    let snapshot = ads(&mut app, |ads| ads.snapshot());
+    assert!(snapshot.rewarded_cooldown_left.is_some());
+    assert_eq!(snapshot.interstitial_pacing_count, 1);
+    let cap = snapshot.frequency_cap.as_ref().unwrap();
+    assert_eq!(cap.shown[&AdType::Interstitial], 1);
+    // The cooldown left is saved rather than the time of the show.
+    let cooldown = cap.cooldowns[&AdType::Interstitial];
+    assert!(cooldown > Duration::ZERO && cooldown <= FrequencyCap::default().min_interval);
+    assert!(!snapshot.ads_enabled);
+
+    let mut restored = mockup_app();
+    restored.insert_resource(FrequencyCap::default());
+    let saved = snapshot.clone();
+    // Later in the fresh app than when the snapshot was taken.
+    advance(&mut restored, Duration::from_secs(5));
+    ads(&mut restored, move |ads| ads.restore(saved));
+    assert_eq!(ads(&mut restored, |ads| ads.snapshot()), snapshot);
+    advance(&mut restored, FRAME);
+    let later = ads(&mut restored, |ads| ads.snapshot());
+    assert_eq!(
+        later.frequency_cap.unwrap().cooldowns[&AdType::Interstitial],
+        cooldown - FRAME
+    );
+}
+
+#[test]
+fn skipping_a_pending_ad_shows_nothing() {
+    let mut app = mockup_app();
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.skip_pending_ad(AdType::Interstitial)));
+    assert!(!ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    let written = take_written(&mut app);
+    assert!(written.iter().any(|message| matches!(
+        message,
+        AdMessage::AdClosed {
+            reason: AdCloseReason::Skipped,
+            ..
+        }
+    )));
+    assert!(
+        !written
+            .iter()
+            .any(|message| matches!(message, AdMessage::AdOpened { .. }))
+    );
+    assert!(displayed(&mut app).is_empty());
+}
+
+#[test]
+fn skipping_a_queued_show_cancels_it() {
+    let mut app = mockup_app();
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| {
+        ads.show_interstitial() && ads.skip_pending_ad(AdType::Interstitial)
+    }));
+    app.update();
+    assert!(displayed(&mut app).is_empty());
+    let written = take_written(&mut app);
+    assert!(written.iter().any(|message| matches!(
+        message,
+        AdMessage::AdClosed {
+            reason: AdCloseReason::Skipped,
+            ..
+        }
+    )));
+    assert!(
+        !written
+            .iter()
+            .any(|message| matches!(message, AdMessage::AdOpened { .. }))
+    );
+    // Once opened the ad is displayed, there is nothing left to skip.
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    assert!(!ads(&mut app, |ads| ads.skip_pending_ad(AdType::Interstitial)));
+    assert_eq!(displayed(&mut app), [AdType::Interstitial]);
+}
+
+#[test]
+fn rewarded_start_carries_the_configured_reward() {
+    let mut app = mockup_app();
+    let reward = Reward {
+        amount: 50,
+        type_name: "gems".to_string(),
+    };
+    app.world_mut()
+        .resource_mut::<MockupAds>()
+        .rewarded_ad_reward = reward.clone();
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_rewarded()));
+    app.update();
+    assert!(take_written(&mut app).iter().any(|message| matches!(
+        message,
+        AdMessage::RewardedAdStarted { expected_reward } if *expected_reward == reward
+    )));
+}
+
+#[test]
+fn gameplay_input_pauses_while_an_ad_is_showing() {
+    #[derive(Resource, Default)]
+    struct Ran(u32);
+
+    let mut app = mockup_app();
+    app.init_resource::<Ran>().add_systems(
+        Update,
+        (|mut ran: ResMut<Ran>| ran.0 += 1).run_if(no_ad_showing),
+    );
+    load(&mut app, AdType::Interstitial);
+    app.world_mut().resource_mut::<Ran>().0 = 0;
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<Ran>().0, 0);
+    ads(&mut app, |ads| ads.hide_interstitial());
+    app.update();
+    assert_eq!(app.world().resource::<Ran>().0, 1);
+}
+
+#[test]
+fn reward_override_replaces_the_configured_reward() {
+    let mut app = mockup_app();
+    let duration = Duration::from_millis(app.world().resource::<MockupAds>().rewarded.duration_ms);
+    app.world_mut()
+        .resource_mut::<MockupAds>()
+        .rewarded_ad_reward = Reward {
+        amount: 10,
+        type_name: "coins".to_string(),
+    };
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_rewarded_with_reward(Reward {
+        amount: 3,
+        type_name: "gems".to_string(),
+    })));
+    advance(&mut app, duration + FRAME);
+    let rewards: Vec<_> = take_written(&mut app)
+        .into_iter()
+        .filter_map(|message| match message {
+            AdMessage::RewardedAdEarnedReward {
+                amount,
+                reward_type,
+            } => Some((amount, reward_type)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(rewards, [(3, "gems".to_string())]);
+}
+
+#[test]
+fn impression_follows_opened_once_laid_out() {
+    let is_opened = |message: &AdMessage| matches!(message, AdMessage::AdOpened { .. });
+    let is_impression = |message: &AdMessage| matches!(message, AdMessage::AdImpression { .. });
+    let mut app = mockup_app();
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    let written = take_written(&mut app);
+    assert!(written.iter().any(is_opened));
+    assert!(!written.iter().any(is_impression));
+    // Headless apps have no UI layout, size the ad as the layout would.
+    let world = app.world_mut();
+    for mut node in world
+        .query_filtered::<&mut ComputedNode, With<MockupAdType>>()
+        .iter_mut(world)
+    {
+        node.size = Vec2::new(320.0, 480.0);
+    }
+    app.update();
+    app.update();
+    let written = take_written(&mut app);
+    assert!(written.iter().any(is_impression));
+    assert!(!written.iter().any(is_opened));
+}
+
+#[test]
+fn rewarded_ad_auto_closes_after_reward_when_set() {
+    for auto_close_after_reward in [true, false] {
+        let mut app = mockup_app();
+        let duration = {
+            let mut cfg = app.world_mut().resource_mut::<MockupAds>();
+            cfg.rewarded.auto_close_after_reward = auto_close_after_reward;
+            Duration::from_millis(cfg.rewarded.duration_ms)
+        };
+        load(&mut app, AdType::Rewarded);
+        assert!(ads(&mut app, |ads| ads.show_rewarded()));
+        advance(&mut app, duration + FRAME);
+        assert_eq!(earned(&mut app).len(), 1);
+        let world = app.world_mut();
+        let close_buttons = world
+            .query_filtered::<(), With<MockupAdCloseButton>>()
+            .iter(world)
+            .count();
+        if auto_close_after_reward {
+            assert!(displayed(&mut app).is_empty());
+        } else {
+            assert_eq!(displayed(&mut app), [AdType::Rewarded]);
+            assert_eq!(close_buttons, 1);
+        }
+    }
+}
+
+#[test]
+fn missing_image_falls_back_to_text() {
+    let mut app = mockup_app();
+    let fallback = {
+        let mut cfg = app.world_mut().resource_mut::<MockupAds>();
+        cfg.interstitial.display = AdDisplay::Image {
+            handle: Handle::default(),
+            fit: ImageFit::Cover,
+        };
+        cfg.image_fallback_text.clone()
+    };
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    let world = app.world_mut();
+    let texts: Vec<_> = world
+        .query::<(&Text, &ChildOf)>()
+        .iter(world)
+        .filter(|(_, parent)| world.get::<MockupAdType>(parent.0).is_some())
+        .map(|(text, _)| text.0.clone())
+        .collect();
+    assert!(texts.contains(&fallback));
+}
+
+#[test]
+fn opt_in_prompt_plays_the_ad_only_on_yes() {
+    for accept in [true, false] {
+        let mut app = mockup_app();
+        app.world_mut()
+            .resource_mut::<MockupAds>()
+            .rewarded
+            .opt_in_prompt = true;
+        load(&mut app, AdType::Rewarded);
+        assert!(ads(&mut app, |ads| ads.show_rewarded()));
+        app.update();
+        assert!(displayed(&mut app).is_empty());
+        let world = app.world_mut();
+        let answer = world
+            .query::<(Entity, &MockupOptInAnswer)>()
+            .iter(world)
+            .find(|(_, answer)| answer.accept == accept)
+            .map(|(entity, _)| entity)
+            .unwrap();
+        click(&mut app, answer);
+        app.update();
+        let written = take_written(&mut app);
+        let dismissed = written.iter().any(|message| {
+            matches!(
+                message,
+                AdMessage::AdClosed {
+                    reason: AdCloseReason::UserDismissed,
+                    ..
+                }
+            )
+        });
+        if accept {
+            assert_eq!(displayed(&mut app), [AdType::Rewarded]);
+            assert!(!dismissed);
+        } else {
+            assert!(displayed(&mut app).is_empty());
+            assert!(dismissed);
+        }
+        let world = app.world_mut();
+        assert!(
+            world
+                .query::<&MockupOptInPrompt>()
+                .iter(world)
+                .next()
+                .is_none()
+        );
+    }
+}
+
+#[cfg(feature = "audio")]
+#[test]
+fn reward_plays_its_audio_cue() {
+    use bevy_audio::AudioPlayer;
+
+    let mut app = mockup_app();
+    app.world_mut()
+        .resource_mut::<MockupAds>()
+        .audio_cues
+        .reward = Some(Handle::default());
+    let queue = app.world().resource::<AdEventQueue>().clone();
+    queue.write(AdMessage::RewardedAdEarnedReward {
+        amount: 1,
+        reward_type: "coins".to_string(),
+    });
+    app.update();
+    app.update();
+    let world = app.world_mut();
+    assert_eq!(world.query::<&AudioPlayer>().iter(world).count(), 1);
+}
+
+/// Entity of the displayed ad of this type.
+fn ad_entity(app: &mut App, ad_type: AdType) -> Entity {
+    app.world_mut()
+        .run_system_once(move |q: Query<(Entity, &MockupAdType)>| active_ad_entity(&q, ad_type))
+        .unwrap()
+        .unwrap()
+}
+
+/// Entities of the children of entities with the component `C`.
+fn children_of<C: Component>(app: &mut App) -> Vec<Entity> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Children, With<C>>()
+        .iter(world)
+        .flat_map(|children| children.iter())
+        .collect()
+}
+
+fn clicked(messages: &[AdMessage]) -> bool {
+    messages
+        .iter()
+        .any(|message| matches!(message, AdMessage::AdClicked { .. }))
+}
+
+#[test]
+fn clicking_an_ad_emits_ad_clicked() {
+    let mut app = mockup_app();
+    assert!(ads(&mut app, |ads| ads.show_banner()));
+    app.update();
+    let banner = ad_entity(&mut app, AdType::Banner);
+    take_written(&mut app);
+    click(&mut app, banner);
+    app.update();
+    assert!(clicked(&take_written(&mut app)));
+}
+
+#[test]
+fn clicking_the_close_button_label_closes_without_a_click() {
+    let mut app = mockup_app();
+    let duration = {
+        let mut cfg = app.world_mut().resource_mut::<MockupAds>();
+        cfg.close_button.label = Some("Close".to_string());
+        Duration::from_millis(cfg.interstitial.duration_ms)
+    };
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    advance(&mut app, duration + FRAME);
+    take_written(&mut app);
+    let label = children_of::<MockupAdCloseButton>(&mut app)[0];
+    click(&mut app, label);
+    app.update();
+    let written = take_written(&mut app);
+    assert!(!clicked(&written));
+    assert!(displayed(&mut app).is_empty());
+}
+
+#[test]
+fn clicking_the_skip_button_label_skips_without_a_click() {
+    let mut app = mockup_app();
+    app.world_mut()
+        .resource_mut::<MockupAds>()
+        .interstitial
+        .skip_after_ms = Some(0);
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    advance(&mut app, FRAME * 2);
+    take_written(&mut app);
+    let label = children_of::<MockupAdSkipButton>(&mut app)[0];
+    click(&mut app, label);
+    app.update();
+    assert!(!clicked(&take_written(&mut app)));
+    assert!(displayed(&mut app).is_empty());
+}
+
+#[test]
+fn loading_again_keeps_the_load_in_flight() {
+    let mut app = mockup_app();
+    assert!(ads(&mut app, |ads| ads.load_ad(AdType::Rewarded, "test")));
+    fn remaining(ads: &mut MockupAdsSystem) -> Duration {
+        ads.timers().rewarded_remaining.unwrap()
+    }
+    let full = ads(&mut app, remaining);
+    advance(&mut app, full / 2);
+    let halfway = ads(&mut app, remaining);
+    assert!(!ads(&mut app, |ads| ads.load_ad(AdType::Rewarded, "test")));
+    assert_eq!(ads(&mut app, remaining), halfway);
+    assert!(halfway < full);
+}
+
+#[test]
+fn first_interstitial_is_skipped_once() {
+    let mut app = mockup_app();
+    app.world_mut()
+        .resource_mut::<MockupAds>()
+        .skip_first_interstitial = true;
+    load(&mut app, AdType::Interstitial);
+    assert!(!ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    assert!(take_written(&mut app).iter().any(|message| matches!(
+        message,
+        AdMessage::AdSuppressed {
+            ad_type: AdType::Interstitial
+        }
+    )));
+    assert!(displayed(&mut app).is_empty());
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    assert_eq!(displayed(&mut app), [AdType::Interstitial]);
+}
+
+#[test]
+fn reward_is_emitted_before_the_close() {
+    let mut app = mockup_app();
+    let duration = {
+        let mut cfg = app.world_mut().resource_mut::<MockupAds>();
+        cfg.rewarded.auto_close = true;
+        Duration::from_millis(cfg.rewarded.duration_ms)
+    };
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_rewarded()));
+    advance(&mut app, duration + FRAME * 2);
+    let written = take_written(&mut app);
+    let position = |f: fn(&AdMessage) -> bool| written.iter().position(f).unwrap();
+    let reward = position(|message| matches!(message, AdMessage::RewardedAdEarnedReward { .. }));
+    let closed = position(|message| matches!(message, AdMessage::AdClosed { .. }));
+    assert!(reward < closed);
+}
+
+#[test]
+fn non_clickable_banner_ignores_taps() {
+    let mut app = mockup_app();
+    app.world_mut().resource_mut::<MockupAds>().banner_clickable = false;
+    assert!(ads(&mut app, |ads| ads.show_banner()));
+    app.update();
+    let banner = ad_entity(&mut app, AdType::Banner);
+    assert!(app.world().get::<Pickable>(banner).is_some());
+    take_written(&mut app);
+    click(&mut app, banner);
+    app.update();
+    assert!(!clicked(&take_written(&mut app)));
+}
+
+#[test]
+fn display_settings_builder_sets_every_field() {
+    let animations = AdAnimations {
+        enter: ShowAnimation::SlideUp,
+        exit: ShowAnimation::Fade,
+        duration_ms: 200,
+    };
+    let settings = AdDisplaySettings::builder()
+        .display(AdDisplay::SolidBackgroundWithText(
+            BackgroundColor(Color::BLACK),
+            "Ad".to_string(),
+        ))
+        .show_time_left(false)
+        .show_progress_bar(true)
+        .auto_close(true)
+        .auto_close_after_reward(true)
+        .opt_in_prompt(true)
+        .duration_ms(5000)
+        .animations(animations)
+        .skip_after_ms(Some(1000))
+        .closeable_after_ms(2000)
+        .scrim(Some(BackgroundColor(Color::BLACK.with_alpha(0.5))))
+        .early_close_reward(EarlyCloseReward::Prorated)
+        .build();
+    assert!(matches!(
+        &settings.display,
+        AdDisplay::SolidBackgroundWithText(_, text) if text == "Ad"
+    ));
+    assert!(!settings.show_time_left);
+    assert!(settings.show_progress_bar);
+    assert!(settings.auto_close);
+    assert!(settings.auto_close_after_reward);
+    assert!(settings.opt_in_prompt);
+    assert_eq!(settings.duration_ms, 5000);
+    assert_eq!(settings.animations, animations);
+    assert_eq!(settings.skip_after_ms, Some(1000));
+    assert_eq!(settings.closeable_after_ms, 2000);
+    assert_eq!(
+        settings.scrim.map(|scrim| scrim.0),
+        Some(Color::BLACK.with_alpha(0.5))
+    );
+    assert_eq!(settings.early_close_reward, EarlyCloseReward::Prorated);
+}
+
+#[test]
+fn fraud_signal_is_only_simulated() {
+    let suspicious = |messages: Vec<AdMessage>| {
+        messages
+            .iter()
+            .any(|message| matches!(message, AdMessage::SuspiciousActivity { .. }))
+    };
+    let mut app = mockup_app();
+    let duration = Duration::from_millis(app.world().resource::<MockupAds>().rewarded.duration_ms);
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_rewarded()));
+    advance(&mut app, duration + FRAME);
+    assert!(!suspicious(take_written(&mut app)));
+    ads(&mut app, |ads| {
+        ads.simulate_fraud_signal("reward claimed too fast")
+    });
+    app.update();
+    assert!(suspicious(take_written(&mut app)));
+}
+
+#[cfg(feature = "config_asset")]
+#[test]
+fn config_asset_is_applied_on_reload() {
+    use bevy_asset::Assets;
+
+    let mut app = mockup_app();
+    let reward = Reward {
+        amount: 25,
+        type_name: "gems".to_string(),
+    };
+    let config = MockupAdsConfig {
+        loading_time_ms: Some(250),
+        rewarded_ad_reward: Some(reward.clone()),
+        ..Default::default()
+    };
+    let handle = app
+        .world_mut()
+        .resource_mut::<Assets<MockupAdsConfig>>()
+        .add(config);
+    app.insert_resource(MockupAdsConfigHandle(handle.clone()));
+    app.update();
+    // Editing the asset is what a hot reload of the file does.
+    app.world_mut()
+        .resource_mut::<Assets<MockupAdsConfig>>()
+        .get_mut(&handle)
+        .unwrap()
+        .loading_time_ms = Some(500);
+    // Asset events are sent after `Update`, the config is applied in the next one.
+    app.update();
+    app.update();
+    let cfg = app.world().resource::<MockupAds>();
+    assert_eq!(cfg.loading_time_ms, 500);
+    assert_eq!(cfg.rewarded_ad_reward, reward);
+}
+
+#[test]
+fn ad_is_gone_only_after_its_exit_animation() {
+    let mut app = mockup_app();
+    let animations = AdAnimations {
+        enter: ShowAnimation::Fade,
+        exit: ShowAnimation::SlideDown,
+        duration_ms: 500,
+    };
+    app.world_mut()
+        .resource_mut::<MockupAds>()
+        .interstitial
+        .animations = animations;
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    let entity = ad_entity(&mut app, AdType::Interstitial);
+    let animation = |app: &mut App| {
+        app.world()
+            .get::<MockupAdAnimation>(entity)
+            .map(|animation| (animation.animation, animation.exit))
+    };
+    assert_eq!(animation(&mut app), Some((ShowAnimation::Fade, false)));
+    advance(&mut app, Duration::from_millis(animations.duration_ms));
+    take_written(&mut app);
+    ads(&mut app, |ads| ads.hide_interstitial());
+    app.update();
+    assert_eq!(animation(&mut app), Some((ShowAnimation::SlideDown, true)));
+    advance(
+        &mut app,
+        Duration::from_millis(animations.duration_ms) - FRAME * 2,
+    );
+    let closed = |messages: Vec<AdMessage>| {
+        messages
+            .iter()
+            .any(|message| matches!(message, AdMessage::AdClosed { .. }))
+    };
+    assert_eq!(displayed(&mut app), [AdType::Interstitial]);
+    assert!(!closed(take_written(&mut app)));
+    advance(&mut app, FRAME * 3);
+    assert!(displayed(&mut app).is_empty());
+    assert!(closed(take_written(&mut app)));
+}
+
+#[test]
+fn explicit_load_starts_the_retries_over() {
+    let mut app = mockup_app();
+    app.world_mut()
+        .resource_mut::<MockupAds>()
+        .network_available = false;
+    app.insert_resource(RetryPolicy {
+        max_attempts: 2,
+        base_backoff_ms: 0,
+        multiplier: 1.0,
+    });
+    let attempts = |app: &mut App| {
+        app.world()
+            .resource::<AdRetryState>()
+            .0
+            .get(&AdType::Rewarded)
+            .map(|retry| (retry.attempts, retry.next_retry.is_some()))
+    };
+    ads(&mut app, |ads| ads.load_ad(AdType::Rewarded, "test"));
+    advance(&mut app, FRAME * 10);
+    // Exhausted after two retries.
+    assert_eq!(attempts(&mut app), Some((2, false)));
+    ads(&mut app, |ads| ads.load_ad(AdType::Rewarded, "test"));
+    assert_eq!(attempts(&mut app), None);
+    advance(&mut app, FRAME * 10);
+    assert_eq!(attempts(&mut app), Some((2, false)));
+}
+
+#[test]
+fn countdowns_follow_their_own_ad() {
+    let mut app = mockup_app();
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    // Only one fullscreen ad is displayed at a time, spawn another ad with a countdown.
+    app.world_mut().spawn((
+        MockupAdComponent {
+            timer: Timer::from_seconds(60.0, TimerMode::Once),
+            auto_close: false,
+        },
+        children![(Text::default(), MockupAdTimeLeftText)],
+    ));
+    advance(&mut app, FRAME * 5);
+    let world = app.world_mut();
+    let mut texts: Vec<_> = world
+        .query_filtered::<&Text, With<MockupAdTimeLeftText>>()
+        .iter(world)
+        .map(|text| text.0.clone())
+        .collect();
+    texts.sort();
+    assert_eq!(texts.len(), 2);
+    assert_ne!(texts[0], texts[1]);
+}
+
+#[test]
+fn banner_node_matches_the_reported_size() {
+    let mut app = mockup_app();
+    app.world_mut().resource_mut::<MockupAds>().banner_size = BannerSize::MediumRectangle;
+    assert!(ads(&mut app, |ads| ads.show_banner()));
+    app.update();
+    let banner = ad_entity(&mut app, AdType::Banner);
+    let size = ads(&mut app, |ads| {
+        (ads.get_banner_width(""), ads.get_banner_height(""))
+    });
+    let node = app.world().get::<Node>(banner).unwrap();
+    assert_eq!(size, (300, 250));
+    assert_eq!(node.width, Val::Px(size.0 as f32));
+    assert_eq!(node.height, Val::Px(size.1 as f32));
+}
+
+#[test]
+fn showing_a_native_ad_opens_it() {
+    let mut app = mockup_app();
+    load(&mut app, AdType::Native);
+    assert!(ads(&mut app, |ads| ads.show_native()));
+    app.update();
+    assert_eq!(displayed(&mut app), [AdType::Native]);
+    assert!(take_written(&mut app).iter().any(|message| matches!(
+        message,
+        AdMessage::AdOpened {
+            ad_type: AdType::Native,
+            ad_id,
+        } if ad_id == "test"
+    )));
+}
+
+#[test]
+fn dismiss_all_leaves_no_ads() {
+    let mut app = mockup_app();
+    load(&mut app, AdType::Interstitial);
+    load(&mut app, AdType::Native);
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    assert!(ads(&mut app, |ads| ads.show_native()));
+    assert!(ads(&mut app, |ads| ads.show_banner()));
+    app.update();
+    assert_eq!(displayed(&mut app).len(), 3);
+    ads(&mut app, |ads| ads.dismiss_all());
+    app.update();
+    assert!(displayed(&mut app).is_empty());
+    assert!(!ads(&mut app, |ads| ads.is_ad_ready(AdType::Rewarded)));
+}
+
+#[test]
+fn hiding_the_banner_closes_it_once() {
+    let mut app = mockup_app();
+    assert!(ads(&mut app, |ads| ads.show_banner()));
+    assert!(ads(&mut app, |ads| ads.show_banner()));
+    app.update();
+    assert_eq!(displayed(&mut app), [AdType::Banner]);
+    take_written(&mut app);
+    assert!(ads(&mut app, |ads| ads.hide_banner()));
+    assert!(ads(&mut app, |ads| ads.hide_banner()));
+    app.update();
+    let closed = take_written(&mut app)
+        .into_iter()
+        .filter(|message| {
+            matches!(
+                message,
+                AdMessage::AdClosed {
+                    ad_type: AdType::Banner,
+                    ..
+                }
+            )
+        })
+        .count();
+    assert_eq!(closed, 1);
+}
+
+#[test]
+fn only_one_fullscreen_ad_is_displayed() {
+    let mut app = mockup_app();
+    load(&mut app, AdType::Interstitial);
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    assert!(!ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    assert_eq!(displayed(&mut app), [AdType::Interstitial]);
+}
+
+#[test]
+fn loading_goes_from_idle_to_loading_to_ready() {
+    let mut app = mockup_app();
+    let state = |app: &mut App| {
+        ads(app, |ads| {
+            (
+                ads.is_loading(AdType::Rewarded),
+                ads.is_ad_ready(AdType::Rewarded),
+            )
+        })
+    };
+    assert_eq!(state(&mut app), (false, false));
+    assert!(ads(&mut app, |ads| ads.load_ad(AdType::Rewarded, "test")));
+    assert_eq!(state(&mut app), (true, false));
+    advance(&mut app, Duration::from_secs(10));
+    assert_eq!(state(&mut app), (false, true));
+}
+
+#[test]
+fn closed_rewarded_ad_earns_nothing_while_leaving() {
+    let mut app = mockup_app();
+    {
+        let mut cfg = app.world_mut().resource_mut::<MockupAds>();
+        cfg.rewarded.auto_close = false;
+        cfg.rewarded.duration_ms = 1000;
+        cfg.rewarded.closeable_after_ms = 200;
+        cfg.rewarded.early_close_reward = EarlyCloseReward::Prorated;
+        cfg.rewarded.animations = AdAnimations {
+            enter: ShowAnimation::None,
+            exit: ShowAnimation::SlideDown,
+            duration_ms: 2000,
+        };
+        cfg.rewarded_ad_reward.amount = 100;
+    }
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_rewarded()));
+    advance(&mut app, Duration::from_millis(500));
+    take_written(&mut app);
+    let world = app.world_mut();
+    let close = world
+        .query_filtered::<Entity, With<MockupAdCloseButton>>()
+        .single(world)
+        .unwrap();
+    click(&mut app, close);
+    app.update();
+    let prorated = earned(&mut app);
+    assert_eq!(prorated.len(), 1);
+    assert!(prorated[0] < 100);
+    advance(&mut app, Duration::from_millis(2500));
+    assert!(displayed(&mut app).is_empty());
+    assert!(earned(&mut app).is_empty());
+}
+
+#[test]
+fn refused_shows_are_not_capped() {
+    let mut app = mockup_app();
+    let mut cap = FrequencyCap::new(Duration::ZERO, 2);
+    cap.ad_types.push(AdType::Native);
+    app.insert_resource(cap);
+    // Native ads aren't fullscreen ads.
+    load(&mut app, AdType::Native);
+    assert!(!ads(&mut app, |ads| ads.show_fullscreen_ad(AdType::Native)));
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    // Refused while the first interstitial is displayed.
+    assert!(!ads(&mut app, |ads| ads.show_interstitial()));
+    let shown = |app: &mut App| {
+        app.world()
+            .resource::<FrequencyCap>()
+            .session(Duration::ZERO)
+            .shown[&AdType::Interstitial]
+    };
+    assert_eq!(shown(&mut app), 1);
+    ads(&mut app, |ads| ads.hide_interstitial());
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    assert_eq!(shown(&mut app), 2);
+    let session = app
+        .world()
+        .resource::<FrequencyCap>()
+        .session(Duration::ZERO);
+    assert!(!session.shown.contains_key(&AdType::Native));
+}
+
+#[test]
+fn panicking_spawn_hook_still_shows_the_ad() {
+    let mut app = mockup_app();
+    app.insert_resource(MockupAdSpawnHook(|_, _| panic!("buggy hook")));
+    load(&mut app, AdType::Interstitial);
+    assert!(ads(&mut app, |ads| ads.show_interstitial()));
+    app.update();
+    assert_eq!(displayed(&mut app), [AdType::Interstitial]);
+    assert!(
+        take_written(&mut app)
+            .iter()
+            .any(|message| matches!(message, AdMessage::AdOpened { .. }))
+    );
+    // Later ads keep working.
+    ads(&mut app, |ads| ads.hide_interstitial());
+    load(&mut app, AdType::Rewarded);
+    assert!(ads(&mut app, |ads| ads.show_rewarded()));
+}
+
+#[test]
+fn seeded_waterfall_is_reproducible() {
+    let winners = |seed| {
+        let mut app = app_with(AdsCommonPlugin::default());
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME));
+        app.insert_resource(
+            MockupAds::builder()
+                .rng_seed(seed)
+                .network("never", 0.0, 100)
+                .network("sometimes", 0.5, 100)
+                .network("always", 1.0, 100)
+                .build(),
+        );
+        app.update();
+        (0..8)
+            .map(|_| {
+                let network = load(&mut app, AdType::Rewarded)
+                    .into_iter()
+                    .find_map(|message| match message {
+                        AdMessage::AdLoaded { network, .. } => network,
+                        _ => None,
+                    });
+                ads(&mut app, |ads| ads.destroy_ad(AdType::Rewarded));
+                network.unwrap()
+            })
+            .collect::<Vec<_>>()
+    };
+    let first = winners(7);
+    assert_eq!(first, winners(7));
+    // A no-fill cascades to the next network, the first one never fills.
+    assert!(first.iter().all(|network| network != "never"));
+    assert!(first.iter().any(|network| network == "sometimes"));
+    assert!(first.iter().any(|network| network == "always"));
+}
+
+#[test]
+fn consent_requirement_follows_the_simulated_region() {
+    let mut app = mockup_app();
+    assert!(ads(&mut app, |ads| ads.consent_required_in_region()));
+    app.world_mut().resource_mut::<MockupAds>().simulated_region = "US".to_string();
+    assert!(!ads(&mut app, |ads| ads.consent_required_in_region()));
+    // Regions without a known requirement don't need consent.
+    app.world_mut().resource_mut::<MockupAds>().simulated_region = "Atlantis".to_string();
+    assert!(!ads(&mut app, |ads| ads.consent_required_in_region()));
+    app.world_mut().resource_mut::<MockupAds>().simulated_region = "UK".to_string();
+    assert!(ads(&mut app, |ads| ads.consent_required_in_region()));
+}
+
+#[test]
+fn show_cycle_is_loaded_opened_closed() {
+    for ad_type in [AdType::Interstitial, AdType::Rewarded, AdType::Native] {
+        let mut app = mockup_app();
+        let mut written = load(&mut app, ad_type);
+        assert!(ads(&mut app, move |ads| match ad_type {
+            AdType::Native => ads.show_native(),
+            _ => ads.show_fullscreen_ad(ad_type),
+        }));
+        app.update();
+        ads(&mut app, move |ads| ads.hide_ad(ad_type));
+        app.update();
+        written.extend(take_written(&mut app));
+        let lifecycle: Vec<_> = written
+            .iter()
+            .filter_map(|message| match message {
+                AdMessage::AdLoaded { .. } => Some("loaded"),
+                AdMessage::AdOpened { .. } => Some("opened"),
+                AdMessage::AdClosed { .. } => Some("closed"),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(lifecycle, ["loaded", "opened", "closed"], "{ad_type}");
+    }
+}
+
+#[test]
+fn only_one_native_ad_is_displayed() {
+    let mut app = mockup_app();
+    load(&mut app, AdType::Native);
+    assert!(ads(&mut app, |ads| ads.show_native()));
+    assert!(!ads(&mut app, |ads| ads.show_native()));
+    app.update();
+    assert_eq!(displayed(&mut app), [AdType::Native]);
+    take_written(&mut app);
+    ads(&mut app, |ads| ads.hide_native());
+    app.update();
+    let closed = take_written(&mut app)
+        .into_iter()
+        .filter(|message| matches!(message, AdMessage::AdClosed { .. }))
+        .count();
+    assert_eq!(closed, 1);
+}
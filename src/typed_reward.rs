@@ -0,0 +1,56 @@
+//! Rewards converted into a user defined currency type.
+
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_ecs::prelude::*;
+
+use crate::{AdMessage, Reward};
+
+/// Reward of a rewarded ad with its reward type parsed into `C`.
+/// Written for every reward owed by an [`AdMessage`] once [`TypedRewardPlugin<C>`] is added.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct TypedReward<C> {
+    pub amount: i32,
+    pub currency: C,
+}
+
+/// Converts rewards into [`TypedReward<C>`] messages using the [`FromStr`] impl of `C`.
+/// Rewards with a type `C` can't parse are only logged.
+/// [`AdMessage::RewardedAdEarnedReward`] is still written as before.
+pub struct TypedRewardPlugin<C>(PhantomData<C>);
+
+impl<C> Default for TypedRewardPlugin<C> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: FromStr + Send + Sync + 'static> Plugin for TypedRewardPlugin<C> {
+    fn build(&self, app: &mut App) {
+        app.add_message::<TypedReward<C>>()
+            .add_systems(Update, convert_rewards::<C>);
+    }
+}
+
+fn convert_rewards<C: FromStr + Send + Sync + 'static>(
+    mut messages: MessageReader<AdMessage>,
+    mut writer: MessageWriter<TypedReward<C>>,
+) {
+    for message in messages.read() {
+        let Some(Reward {
+            amount,
+            type_name: reward_type,
+        }) = message.owed_reward()
+        else {
+            continue;
+        };
+        match reward_type.parse() {
+            Ok(currency) => {
+                writer.write(TypedReward { amount, currency });
+            }
+            Err(_) => bevy_log::warn!("Unknown reward type {reward_type}"),
+        }
+    }
+}